@@ -0,0 +1,734 @@
+use serde_json::{json, Value};
+use solana_program::program_option::COption;
+use solana_program::program_utils::limited_deserialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction::SystemInstruction;
+use solana_program::nonce::state::{State as NonceState, Versions as NonceVersions};
+use solana_program::program_pack::Pack;
+use spl_token_2022::extension::cpi_guard::CpiGuard;
+use spl_token_2022::extension::default_account_state::DefaultAccountState;
+use spl_token_2022::extension::interest_bearing_mint::InterestBearingConfig;
+use spl_token_2022::extension::memo_transfer::MemoTransfer;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::instruction::TransferFeeInstruction;
+use spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{AccountType as TokenAccountType, BaseState, BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::instruction::{TokenInstruction, MAX_SIGNERS};
+use spl_token_2022::state::{Account, AccountState, Mint, Multisig};
+
+/// Human-readable view of one instruction of a program this API knows about.
+pub struct DecodedInstruction {
+    pub program: &'static str,
+    pub instruction_type: String,
+    pub info: Value,
+    account_roles: Vec<&'static str>,
+    remaining_role: &'static str,
+}
+
+impl DecodedInstruction {
+    fn new(program: &'static str, instruction_type: &str, info: Value, account_roles: &[&'static str], remaining_role: &'static str) -> Self {
+        DecodedInstruction {
+            program,
+            instruction_type: instruction_type.to_string(),
+            info,
+            account_roles: account_roles.to_vec(),
+            remaining_role,
+        }
+    }
+
+    /// Role of the account at `index`; accounts past the fixed layout are numbered,
+    /// e.g. the signers of a multisig authority.
+    pub fn account_role(&self, index: usize) -> String {
+        match self.account_roles.get(index) {
+            Some(role) => role.to_string(),
+            None => format!("{}_{}", self.remaining_role, index - self.account_roles.len() + 1),
+        }
+    }
+}
+
+pub fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    if *program_id == solana_program::system_program::id() {
+        Some("system")
+    } else if *program_id == spl_token::id() {
+        Some("spl-token")
+    } else if *program_id == spl_token_2022::id() {
+        Some("token-2022")
+    } else if *program_id == spl_associated_token_account::id() {
+        Some("associated-token-account")
+    } else if *program_id == spl_memo::id() || *program_id == spl_memo::v1::id() {
+        Some("memo")
+    } else if *program_id == solana_sdk::compute_budget::id() {
+        Some("compute-budget")
+    } else {
+        None
+    }
+}
+
+pub fn decode_instruction(program_id: &Pubkey, data: &[u8]) -> Result<DecodedInstruction, String> {
+    match program_name(program_id) {
+        Some("system") => decode_system(data),
+        Some("spl-token") => decode_token("spl-token", data),
+        Some("token-2022") => decode_token("token-2022", data),
+        Some("associated-token-account") => decode_associated_token_account(data),
+        Some("memo") => decode_memo(data),
+        Some("compute-budget") => decode_compute_budget(data),
+        _ => Err(format!("Unsupported program: {}", program_id)),
+    }
+}
+
+fn coption_to_json(value: &COption<Pubkey>) -> Value {
+    match value {
+        COption::Some(pk) => json!(pk.to_string()),
+        COption::None => Value::Null,
+    }
+}
+
+// Token-2022 extensions store optional pubkeys as 32 bytes, all zero meaning none
+fn optional_nonzero_pubkey(bytes: &[u8]) -> Option<Value> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    if bytes == [0u8; 32] {
+        Some(Value::Null)
+    } else {
+        Some(json!(Pubkey::new_from_array(bytes).to_string()))
+    }
+}
+
+fn lower_first(name: String) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+fn decode_system(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let ix: SystemInstruction = limited_deserialize(data, solana_sdk::packet::PACKET_DATA_SIZE as u64)
+        .map_err(|_| "Invalid system instruction data".to_string())?;
+    let decoded = match ix {
+        SystemInstruction::CreateAccount { lamports, space, owner } => DecodedInstruction::new(
+            "system", "createAccount",
+            json!({ "lamports": lamports, "space": space, "owner": owner.to_string() }),
+            &["funding_account", "new_account"], "account",
+        ),
+        SystemInstruction::Assign { owner } => DecodedInstruction::new(
+            "system", "assign",
+            json!({ "owner": owner.to_string() }),
+            &["account"], "account",
+        ),
+        SystemInstruction::Transfer { lamports } => DecodedInstruction::new(
+            "system", "transfer",
+            json!({ "lamports": lamports }),
+            &["source", "destination"], "account",
+        ),
+        SystemInstruction::CreateAccountWithSeed { base, seed, lamports, space, owner } => DecodedInstruction::new(
+            "system", "createAccountWithSeed",
+            json!({ "base": base.to_string(), "seed": seed, "lamports": lamports, "space": space, "owner": owner.to_string() }),
+            &["funding_account", "created_account", "base_account"], "account",
+        ),
+        SystemInstruction::AdvanceNonceAccount => DecodedInstruction::new(
+            "system", "advanceNonce",
+            json!({}),
+            &["nonce_account", "recent_blockhashes_sysvar", "nonce_authority"], "account",
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => DecodedInstruction::new(
+            "system", "withdrawFromNonce",
+            json!({ "lamports": lamports }),
+            &["nonce_account", "destination", "recent_blockhashes_sysvar", "rent_sysvar", "nonce_authority"], "account",
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => DecodedInstruction::new(
+            "system", "initializeNonce",
+            json!({ "nonceAuthority": authority.to_string() }),
+            &["nonce_account", "recent_blockhashes_sysvar", "rent_sysvar"], "account",
+        ),
+        SystemInstruction::AuthorizeNonceAccount(authority) => DecodedInstruction::new(
+            "system", "authorizeNonce",
+            json!({ "newAuthorized": authority.to_string() }),
+            &["nonce_account", "nonce_authority"], "account",
+        ),
+        SystemInstruction::Allocate { space } => DecodedInstruction::new(
+            "system", "allocate",
+            json!({ "space": space }),
+            &["account"], "account",
+        ),
+        SystemInstruction::AllocateWithSeed { base, seed, space, owner } => DecodedInstruction::new(
+            "system", "allocateWithSeed",
+            json!({ "base": base.to_string(), "seed": seed, "space": space, "owner": owner.to_string() }),
+            &["allocated_account", "base_account"], "account",
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => DecodedInstruction::new(
+            "system", "assignWithSeed",
+            json!({ "base": base.to_string(), "seed": seed, "owner": owner.to_string() }),
+            &["account", "base_account"], "account",
+        ),
+        SystemInstruction::TransferWithSeed { lamports, from_seed, from_owner } => DecodedInstruction::new(
+            "system", "transferWithSeed",
+            json!({ "lamports": lamports, "fromSeed": from_seed, "fromOwner": from_owner.to_string() }),
+            &["source", "source_base", "destination"], "account",
+        ),
+        SystemInstruction::UpgradeNonceAccount => DecodedInstruction::new(
+            "system", "upgradeNonce",
+            json!({}),
+            &["nonce_account"], "account",
+        ),
+    };
+    Ok(decoded)
+}
+
+// the legacy program understands everything up to UiAmountToAmount
+const LAST_SPL_TOKEN_INSTRUCTION: u8 = 24;
+
+#[allow(deprecated)]
+fn decode_token(program: &'static str, data: &[u8]) -> Result<DecodedInstruction, String> {
+    if program == "spl-token" && data.first().is_some_and(|tag| *tag > LAST_SPL_TOKEN_INSTRUCTION) {
+        return Err("Invalid spl-token instruction data".to_string());
+    }
+    let ix = TokenInstruction::unpack(data).map_err(|_| format!("Invalid {} instruction data", program))?;
+    let new = |name: &str, info: Value, roles: &[&'static str], remaining: &'static str| {
+        DecodedInstruction::new(program, name, info, roles, remaining)
+    };
+    let decoded = match ix {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => new(
+            "initializeMint",
+            json!({ "decimals": decimals, "mintAuthority": mint_authority.to_string(), "freezeAuthority": coption_to_json(&freeze_authority) }),
+            &["mint", "rent_sysvar"], "account",
+        ),
+        TokenInstruction::InitializeAccount => new(
+            "initializeAccount", json!({}),
+            &["account", "mint", "owner", "rent_sysvar"], "account",
+        ),
+        TokenInstruction::InitializeMultisig { m } => new(
+            "initializeMultisig", json!({ "m": m }),
+            &["multisig", "rent_sysvar"], "signer",
+        ),
+        TokenInstruction::Transfer { amount } => new(
+            "transfer", json!({ "amount": amount }),
+            &["source", "destination", "authority"], "signer",
+        ),
+        TokenInstruction::Approve { amount } => new(
+            "approve", json!({ "amount": amount }),
+            &["source", "delegate", "owner"], "signer",
+        ),
+        TokenInstruction::Revoke => new(
+            "revoke", json!({}),
+            &["source", "owner"], "signer",
+        ),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => new(
+            "setAuthority",
+            json!({ "authorityType": lower_first(format!("{:?}", authority_type)), "newAuthority": coption_to_json(&new_authority) }),
+            &["owned_account", "current_authority"], "signer",
+        ),
+        TokenInstruction::MintTo { amount } => new(
+            "mintTo", json!({ "amount": amount }),
+            &["mint", "destination", "authority"], "signer",
+        ),
+        TokenInstruction::Burn { amount } => new(
+            "burn", json!({ "amount": amount }),
+            &["account", "mint", "authority"], "signer",
+        ),
+        TokenInstruction::CloseAccount => new(
+            "closeAccount", json!({}),
+            &["account", "destination", "owner"], "signer",
+        ),
+        TokenInstruction::FreezeAccount => new(
+            "freezeAccount", json!({}),
+            &["account", "mint", "freeze_authority"], "signer",
+        ),
+        TokenInstruction::ThawAccount => new(
+            "thawAccount", json!({}),
+            &["account", "mint", "freeze_authority"], "signer",
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => new(
+            "transferChecked", json!({ "amount": amount, "decimals": decimals }),
+            &["source", "mint", "destination", "authority"], "signer",
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => new(
+            "approveChecked", json!({ "amount": amount, "decimals": decimals }),
+            &["source", "mint", "delegate", "owner"], "signer",
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => new(
+            "mintToChecked", json!({ "amount": amount, "decimals": decimals }),
+            &["mint", "destination", "authority"], "signer",
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => new(
+            "burnChecked", json!({ "amount": amount, "decimals": decimals }),
+            &["account", "mint", "authority"], "signer",
+        ),
+        TokenInstruction::InitializeAccount2 { owner } => new(
+            "initializeAccount2", json!({ "owner": owner.to_string() }),
+            &["account", "mint", "rent_sysvar"], "account",
+        ),
+        TokenInstruction::SyncNative => new(
+            "syncNative", json!({}),
+            &["account"], "account",
+        ),
+        TokenInstruction::InitializeAccount3 { owner } => new(
+            "initializeAccount3", json!({ "owner": owner.to_string() }),
+            &["account", "mint"], "account",
+        ),
+        TokenInstruction::InitializeMultisig2 { m } => new(
+            "initializeMultisig2", json!({ "m": m }),
+            &["multisig"], "signer",
+        ),
+        TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => new(
+            "initializeMint2",
+            json!({ "decimals": decimals, "mintAuthority": mint_authority.to_string(), "freezeAuthority": coption_to_json(&freeze_authority) }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::GetAccountDataSize { extension_types } => new(
+            "getAccountDataSize",
+            json!({ "extensionTypes": extension_types.iter().map(|t| lower_first(format!("{:?}", t))).collect::<Vec<_>>() }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::InitializeImmutableOwner => new(
+            "initializeImmutableOwner", json!({}),
+            &["account"], "account",
+        ),
+        TokenInstruction::AmountToUiAmount { amount } => new(
+            "amountToUiAmount", json!({ "amount": amount }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::UiAmountToAmount { ui_amount } => new(
+            "uiAmountToAmount", json!({ "uiAmount": ui_amount }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::InitializeMintCloseAuthority { close_authority } => new(
+            "initializeMintCloseAuthority", json!({ "closeAuthority": coption_to_json(&close_authority) }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::TransferFeeExtension(fee_ix) => decode_transfer_fee(program, fee_ix),
+        TokenInstruction::DefaultAccountStateExtension => {
+            let state = match data.get(2) {
+                Some(0) => "uninitialized",
+                Some(1) => "initialized",
+                Some(2) => "frozen",
+                _ => return Err("Invalid default account state data".to_string()),
+            };
+            match data.get(1) {
+                Some(0) => new("initializeDefaultAccountState", json!({ "accountState": state }), &["mint"], "account"),
+                Some(1) => new("updateDefaultAccountState", json!({ "accountState": state }), &["mint", "freeze_authority"], "signer"),
+                _ => return Err("Invalid default account state data".to_string()),
+            }
+        }
+        TokenInstruction::Reallocate { extension_types } => new(
+            "reallocate",
+            json!({ "extensionTypes": extension_types.iter().map(|t| lower_first(format!("{:?}", t))).collect::<Vec<_>>() }),
+            &["account", "payer", "system_program", "owner"], "signer",
+        ),
+        TokenInstruction::MemoTransferExtension => new(
+            match data.get(1) {
+                Some(0) => "enableRequiredMemoTransfers",
+                _ => "disableRequiredMemoTransfers",
+            },
+            json!({}),
+            &["account", "owner"], "signer",
+        ),
+        TokenInstruction::CreateNativeMint => new(
+            "createNativeMint", json!({}),
+            &["payer", "native_mint", "system_program"], "account",
+        ),
+        TokenInstruction::InitializeNonTransferableMint => new(
+            "initializeNonTransferableMint", json!({}),
+            &["mint"], "account",
+        ),
+        TokenInstruction::InterestBearingMintExtension => match data.get(1) {
+            Some(0) if data.len() >= 36 => new(
+                "initializeInterestBearingConfig",
+                json!({
+                    "rateAuthority": optional_nonzero_pubkey(&data[2..34]),
+                    "rate": i16::from_le_bytes([data[34], data[35]]),
+                }),
+                &["mint"], "account",
+            ),
+            Some(1) if data.len() >= 4 => new(
+                "updateInterestBearingConfigRate",
+                json!({ "rate": i16::from_le_bytes([data[2], data[3]]) }),
+                &["mint", "rate_authority"], "signer",
+            ),
+            _ => return Err("Invalid interest-bearing mint data".to_string()),
+        },
+        TokenInstruction::CpiGuardExtension => new(
+            match data.get(1) {
+                Some(0) => "enableCpiGuard",
+                _ => "disableCpiGuard",
+            },
+            json!({}),
+            &["account", "owner"], "signer",
+        ),
+        TokenInstruction::InitializePermanentDelegate { delegate } => new(
+            "initializePermanentDelegate", json!({ "delegate": delegate.to_string() }),
+            &["mint"], "account",
+        ),
+        TokenInstruction::WithdrawExcessLamports => new(
+            "withdrawExcessLamports", json!({}),
+            &["source", "destination", "authority"], "signer",
+        ),
+        TokenInstruction::MetadataPointerExtension => match data.get(1) {
+            Some(0) if data.len() >= 66 => new(
+                "initializeMetadataPointer",
+                json!({
+                    "authority": optional_nonzero_pubkey(&data[2..34]),
+                    "metadataAddress": optional_nonzero_pubkey(&data[34..66]),
+                }),
+                &["mint"], "account",
+            ),
+            Some(1) if data.len() >= 34 => new(
+                "updateMetadataPointer",
+                json!({ "metadataAddress": optional_nonzero_pubkey(&data[2..34]) }),
+                &["mint", "authority"], "signer",
+            ),
+            _ => return Err("Invalid metadata pointer data".to_string()),
+        },
+        // extensions without a structured decoding only report their sub-instruction tag
+        other => new(
+            &lower_first(format!("{:?}", other)),
+            json!({ "extensionInstruction": data.get(1) }),
+            &[], "account",
+        ),
+    };
+    Ok(decoded)
+}
+
+fn decode_transfer_fee(program: &'static str, ix: TransferFeeInstruction) -> DecodedInstruction {
+    match ix {
+        TransferFeeInstruction::InitializeTransferFeeConfig {
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => DecodedInstruction::new(
+            program, "initializeTransferFeeConfig",
+            json!({
+                "transferFeeConfigAuthority": coption_to_json(&transfer_fee_config_authority),
+                "withdrawWithheldAuthority": coption_to_json(&withdraw_withheld_authority),
+                "transferFeeBasisPoints": transfer_fee_basis_points,
+                "maximumFee": maximum_fee,
+            }),
+            &["mint"], "account",
+        ),
+        TransferFeeInstruction::TransferCheckedWithFee { amount, decimals, fee } => DecodedInstruction::new(
+            program, "transferCheckedWithFee",
+            json!({ "amount": amount, "decimals": decimals, "fee": fee }),
+            &["source", "mint", "destination", "authority"], "signer",
+        ),
+        TransferFeeInstruction::WithdrawWithheldTokensFromMint => DecodedInstruction::new(
+            program, "withdrawWithheldTokensFromMint",
+            json!({}),
+            &["mint", "destination", "withdraw_withheld_authority"], "signer",
+        ),
+        TransferFeeInstruction::WithdrawWithheldTokensFromAccounts { num_token_accounts } => DecodedInstruction::new(
+            program, "withdrawWithheldTokensFromAccounts",
+            json!({ "numTokenAccounts": num_token_accounts }),
+            &["mint", "destination", "withdraw_withheld_authority"], "account",
+        ),
+        TransferFeeInstruction::HarvestWithheldTokensToMint => DecodedInstruction::new(
+            program, "harvestWithheldTokensToMint",
+            json!({}),
+            &["mint"], "source",
+        ),
+        TransferFeeInstruction::SetTransferFee { transfer_fee_basis_points, maximum_fee } => DecodedInstruction::new(
+            program, "setTransferFee",
+            json!({ "transferFeeBasisPoints": transfer_fee_basis_points, "maximumFee": maximum_fee }),
+            &["mint", "transfer_fee_config_authority"], "signer",
+        ),
+    }
+}
+
+fn decode_associated_token_account(data: &[u8]) -> Result<DecodedInstruction, String> {
+    // an empty payload is the original Create instruction
+    match data.first() {
+        None | Some(0) if data.len() <= 1 => Ok(DecodedInstruction::new(
+            "associated-token-account", "create", json!({}),
+            &["funding_account", "associated_token_account", "wallet", "mint", "system_program", "token_program"], "account",
+        )),
+        Some(1) if data.len() == 1 => Ok(DecodedInstruction::new(
+            "associated-token-account", "createIdempotent", json!({}),
+            &["funding_account", "associated_token_account", "wallet", "mint", "system_program", "token_program"], "account",
+        )),
+        Some(2) if data.len() == 1 => Ok(DecodedInstruction::new(
+            "associated-token-account", "recoverNested", json!({}),
+            &["nested_account", "nested_mint", "destination_account", "owner_account", "owner_mint", "wallet", "token_program"], "account",
+        )),
+        _ => Err("Invalid associated-token-account instruction data".to_string()),
+    }
+}
+
+fn decode_memo(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let memo = std::str::from_utf8(data).map_err(|_| "Memo is not valid UTF-8".to_string())?;
+    Ok(DecodedInstruction::new("memo", "memo", json!({ "memo": memo }), &[], "signer"))
+}
+
+fn decode_compute_budget(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let u32_at = |offset: usize| data.get(offset..offset + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let u64_at = |offset: usize| data.get(offset..offset + 8).map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+    let invalid = || "Invalid compute-budget instruction data".to_string();
+    let (name, info) = match data.first() {
+        Some(0) => ("requestUnits", json!({
+            "units": u32_at(1).ok_or_else(invalid)?,
+            "additionalFee": u32_at(5).ok_or_else(invalid)?,
+        })),
+        Some(1) => ("requestHeapFrame", json!({ "bytes": u32_at(1).ok_or_else(invalid)? })),
+        Some(2) => ("setComputeUnitLimit", json!({ "units": u32_at(1).ok_or_else(invalid)? })),
+        Some(3) => ("setComputeUnitPrice", json!({ "microLamports": u64_at(1).ok_or_else(invalid)? })),
+        Some(4) => ("setLoadedAccountsDataSizeLimit", json!({ "bytes": u32_at(1).ok_or_else(invalid)? })),
+        _ => return Err(invalid()),
+    };
+    Ok(DecodedInstruction::new("compute-budget", name, info, &[], "account"))
+}
+
+/// Typed view of the data of an account owned by a program this API knows about.
+pub struct DecodedAccount {
+    pub program: &'static str,
+    pub account_type: &'static str,
+    pub info: Value,
+}
+
+pub fn decode_account(owner: &Pubkey, data: &[u8]) -> Result<DecodedAccount, String> {
+    match program_name(owner) {
+        Some("system") => decode_nonce_account(data),
+        Some("spl-token") => decode_token_account(data),
+        Some("token-2022") => decode_token_2022_account(data),
+        _ => Err(format!("Unsupported owner program: {}", owner)),
+    }
+}
+
+fn account_state_name(state: AccountState) -> &'static str {
+    match state {
+        AccountState::Uninitialized => "uninitialized",
+        AccountState::Initialized => "initialized",
+        AccountState::Frozen => "frozen",
+    }
+}
+
+fn mint_info(mint: &Mint) -> Value {
+    json!({
+        "mintAuthority": coption_to_json(&mint.mint_authority),
+        "supply": mint.supply,
+        "decimals": mint.decimals,
+        "isInitialized": mint.is_initialized,
+        "freezeAuthority": coption_to_json(&mint.freeze_authority),
+    })
+}
+
+fn token_account_info(account: &Account) -> Value {
+    json!({
+        "mint": account.mint.to_string(),
+        "owner": account.owner.to_string(),
+        "amount": account.amount,
+        "delegate": coption_to_json(&account.delegate),
+        "state": account_state_name(account.state),
+        // native accounts hold their rent-exempt reserve here
+        "isNative": account.is_native.is_some(),
+        "rentExemptReserve": match account.is_native {
+            COption::Some(reserve) => json!(reserve),
+            COption::None => Value::Null,
+        },
+        "delegatedAmount": account.delegated_amount,
+        "closeAuthority": coption_to_json(&account.close_authority),
+    })
+}
+
+// unpack only checks the initialized flag, so m and n come straight from the caller's bytes
+fn multisig_info(multisig: &Multisig) -> Result<Value, String> {
+    let n = multisig.n as usize;
+    if n > MAX_SIGNERS || multisig.m == 0 || multisig.m > multisig.n {
+        return Err("Invalid multisig account data".to_string());
+    }
+    let signers: Vec<String> = multisig.signers
+        .get(..n)
+        .ok_or_else(|| "Invalid multisig account data".to_string())?
+        .iter()
+        .map(|pk| pk.to_string())
+        .collect();
+    Ok(json!({
+        "numRequiredSigners": multisig.m,
+        "numValidSigners": multisig.n,
+        "isInitialized": multisig.is_initialized,
+        "signers": signers,
+    }))
+}
+
+// spl-token and Token-2022 share the base layouts, so the kind follows from the length
+fn decode_token_account(data: &[u8]) -> Result<DecodedAccount, String> {
+    let (account_type, info) = match data.len() {
+        Mint::LEN => ("mint", mint_info(&Mint::unpack(data).map_err(|_| "Invalid mint account data".to_string())?)),
+        Account::LEN => ("account", token_account_info(&Account::unpack(data).map_err(|_| "Invalid token account data".to_string())?)),
+        Multisig::LEN => ("multisig", multisig_info(&Multisig::unpack(data).map_err(|_| "Invalid multisig account data".to_string())?)?),
+        len => return Err(format!("Unexpected token account data length: {}", len)),
+    };
+    Ok(DecodedAccount { program: "spl-token", account_type, info })
+}
+
+fn decode_token_2022_account(data: &[u8]) -> Result<DecodedAccount, String> {
+    if data.len() == Multisig::LEN {
+        let multisig = Multisig::unpack(data).map_err(|_| "Invalid multisig account data".to_string())?;
+        return Ok(DecodedAccount { program: "token-2022", account_type: "multisig", info: multisig_info(&multisig)? });
+    }
+    // past the base account length, the account type byte tells mints and accounts apart
+    let is_mint = data.len() == Mint::LEN || (data.len() > Account::LEN && data[Account::LEN] == TokenAccountType::Mint as u8);
+    let (account_type, mut info, extensions) = if is_mint {
+        let state = StateWithExtensions::<Mint>::unpack(data).map_err(|_| "Invalid mint account data".to_string())?;
+        ("mint", mint_info(&state.base), extensions_info(&state)?)
+    } else {
+        let state = StateWithExtensions::<Account>::unpack(data).map_err(|_| "Invalid token account data".to_string())?;
+        ("account", token_account_info(&state.base), extensions_info(&state)?)
+    };
+    info["extensions"] = json!(extensions);
+    Ok(DecodedAccount { program: "token-2022", account_type, info })
+}
+
+fn pubkey_option_to_json(value: Option<Pubkey>) -> Value {
+    match value {
+        Some(pk) => json!(pk.to_string()),
+        None => Value::Null,
+    }
+}
+
+fn transfer_fee_json(fee: &TransferFee) -> Value {
+    json!({
+        "epoch": u64::from(fee.epoch),
+        "maximumFee": u64::from(fee.maximum_fee),
+        "transferFeeBasisPoints": u16::from(fee.transfer_fee_basis_points),
+    })
+}
+
+fn extensions_info<S: BaseState, T: BaseStateWithExtensions<S>>(state: &T) -> Result<Vec<Value>, String> {
+    let types = state.get_extension_types().map_err(|_| "Invalid extension data".to_string())?;
+    types
+        .into_iter()
+        .map(|extension_type| {
+            let name = lower_first(format!("{:?}", extension_type));
+            let invalid = |_| format!("Invalid {} extension data", name);
+            let state = match extension_type {
+                ExtensionType::TransferFeeConfig => {
+                    let config = state.get_extension::<TransferFeeConfig>().map_err(invalid)?;
+                    json!({
+                        "transferFeeConfigAuthority": pubkey_option_to_json(config.transfer_fee_config_authority.into()),
+                        "withdrawWithheldAuthority": pubkey_option_to_json(config.withdraw_withheld_authority.into()),
+                        "withheldAmount": u64::from(config.withheld_amount),
+                        "olderTransferFee": transfer_fee_json(&config.older_transfer_fee),
+                        "newerTransferFee": transfer_fee_json(&config.newer_transfer_fee),
+                    })
+                }
+                ExtensionType::TransferFeeAmount => {
+                    let amount = state.get_extension::<TransferFeeAmount>().map_err(invalid)?;
+                    json!({ "withheldAmount": u64::from(amount.withheld_amount) })
+                }
+                ExtensionType::MintCloseAuthority => {
+                    let authority = state.get_extension::<MintCloseAuthority>().map_err(invalid)?;
+                    json!({ "closeAuthority": pubkey_option_to_json(authority.close_authority.into()) })
+                }
+                ExtensionType::PermanentDelegate => {
+                    let delegate = state.get_extension::<PermanentDelegate>().map_err(invalid)?;
+                    json!({ "delegate": pubkey_option_to_json(delegate.delegate.into()) })
+                }
+                ExtensionType::DefaultAccountState => {
+                    let default_state = state.get_extension::<DefaultAccountState>().map_err(invalid)?;
+                    let account_state = AccountState::try_from(default_state.state).map_err(|_| format!("Invalid {} extension data", name))?;
+                    json!({ "accountState": account_state_name(account_state) })
+                }
+                ExtensionType::InterestBearingConfig => {
+                    let config = state.get_extension::<InterestBearingConfig>().map_err(invalid)?;
+                    json!({
+                        "rateAuthority": pubkey_option_to_json(config.rate_authority.into()),
+                        "initializationTimestamp": i64::from(config.initialization_timestamp),
+                        "preUpdateAverageRate": i16::from(config.pre_update_average_rate),
+                        "lastUpdateTimestamp": i64::from(config.last_update_timestamp),
+                        "currentRate": i16::from(config.current_rate),
+                    })
+                }
+                ExtensionType::MetadataPointer => {
+                    let pointer = state.get_extension::<MetadataPointer>().map_err(invalid)?;
+                    json!({
+                        "authority": pubkey_option_to_json(pointer.authority.into()),
+                        "metadataAddress": pubkey_option_to_json(pointer.metadata_address.into()),
+                    })
+                }
+                ExtensionType::TransferHook => {
+                    let hook = state.get_extension::<TransferHook>().map_err(invalid)?;
+                    json!({
+                        "authority": pubkey_option_to_json(hook.authority.into()),
+                        "programId": pubkey_option_to_json(hook.program_id.into()),
+                    })
+                }
+                ExtensionType::MemoTransfer => {
+                    let memo = state.get_extension::<MemoTransfer>().map_err(invalid)?;
+                    json!({ "requireIncomingTransferMemos": bool::from(memo.require_incoming_transfer_memos) })
+                }
+                ExtensionType::CpiGuard => {
+                    let guard = state.get_extension::<CpiGuard>().map_err(invalid)?;
+                    json!({ "lockCpi": bool::from(guard.lock_cpi) })
+                }
+                // marker extensions carry no state; the rest are reported by name only
+                _ => Value::Null,
+            };
+            Ok(json!({ "extension": name, "state": state }))
+        })
+        .collect()
+}
+
+fn decode_nonce_account(data: &[u8]) -> Result<DecodedAccount, String> {
+    if data.len() != NonceState::size() {
+        return Err(format!("Unexpected nonce account data length: {}", data.len()));
+    }
+    let versions: NonceVersions = bincode::deserialize(data).map_err(|_| "Invalid nonce account data".to_string())?;
+    let version = match versions {
+        NonceVersions::Legacy(_) => "legacy",
+        NonceVersions::Current(_) => "current",
+    };
+    let (account_type, info) = match versions.state() {
+        NonceState::Uninitialized => ("uninitialized", json!({ "version": version })),
+        NonceState::Initialized(nonce) => (
+            "initialized",
+            json!({
+                "version": version,
+                "authority": nonce.authority.to_string(),
+                "blockhash": nonce.blockhash().to_string(),
+                "feeCalculator": { "lamportsPerSignature": nonce.fee_calculator.lamports_per_signature },
+            }),
+        ),
+    };
+    Ok(DecodedAccount { program: "nonce", account_type, info })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // initialized multisig bytes: m, n, is_initialized, then eleven signer keys
+    fn multisig_data(m: u8, n: u8) -> Vec<u8> {
+        let mut data = vec![0u8; Multisig::LEN];
+        data[0] = m;
+        data[1] = n;
+        data[2] = 1;
+        for (i, chunk) in data[3..].chunks_mut(32).enumerate() {
+            chunk.copy_from_slice(Pubkey::new_from_array([i as u8 + 1; 32]).as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_multisig() {
+        let decoded = decode_account(&spl_token::id(), &multisig_data(2, 3)).unwrap();
+        assert_eq!(decoded.account_type, "multisig");
+        assert_eq!(decoded.info["signers"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn rejects_multisig_with_too_many_signers() {
+        for owner in [spl_token::id(), spl_token_2022::id()] {
+            let result = decode_account(&owner, &multisig_data(1, MAX_SIGNERS as u8 + 1));
+            assert_eq!(result.err().as_deref(), Some("Invalid multisig account data"));
+        }
+    }
+
+    #[test]
+    fn rejects_multisig_with_invalid_threshold() {
+        for (m, n) in [(0, 3), (4, 3)] {
+            let result = decode_account(&spl_token::id(), &multisig_data(m, n));
+            assert_eq!(result.err().as_deref(), Some("Invalid multisig account data"));
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use ed25519_dalek::{PublicKey as DalekPublicKey, Signature as DalekSignature};
+
+/// Why a signature failed strict verification, checked in this order.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    NonCanonicalS,
+    InvalidPublicKey,
+    InvalidR,
+    SmallOrderPublicKey,
+    SmallOrderR,
+    Mismatch,
+}
+
+impl Rejection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rejection::NonCanonicalS => "non_canonical_s",
+            Rejection::InvalidPublicKey => "invalid_public_key",
+            Rejection::InvalidR => "invalid_r",
+            Rejection::SmallOrderPublicKey => "small_order_public_key",
+            Rejection::SmallOrderR => "small_order_r",
+            Rejection::Mismatch => "mismatch",
+        }
+    }
+}
+
+/// Verifies like `verify_strict`, which only says no, and names the check that failed.
+///
+/// Unlike `verify` this refuses S >= L, which would make signatures malleable, and
+/// small-order keys or R points, which let one signature pass for many messages.
+pub fn verify_strict(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<(), Rejection> {
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&signature[32..]);
+    if Scalar::from_canonical_bytes(s).is_none() {
+        return Err(Rejection::NonCanonicalS);
+    }
+    let public_point = CompressedEdwardsY(*pubkey).decompress().ok_or(Rejection::InvalidPublicKey)?;
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    let r_point = CompressedEdwardsY(r).decompress().ok_or(Rejection::InvalidR)?;
+    if public_point.is_small_order() {
+        return Err(Rejection::SmallOrderPublicKey);
+    }
+    if r_point.is_small_order() {
+        return Err(Rejection::SmallOrderR);
+    }
+    let public_key = DalekPublicKey::from_bytes(pubkey).map_err(|_| Rejection::InvalidPublicKey)?;
+    let signature = DalekSignature::from_bytes(signature).map_err(|_| Rejection::NonCanonicalS)?;
+    public_key.verify_strict(message, &signature).map_err(|_| Rejection::Mismatch)
+}
+
+/// Whether both the public key and R decode to points of prime order, i.e. neither small-order
+/// nor carrying a small-order component.
+///
+/// Batch verification drops the cofactor and weights each entry randomly, so a torsion
+/// component can make it disagree with `verify`; for prime-order points the two agree.
+pub fn prime_order(pubkey: &[u8; 32], signature: &[u8; 64]) -> bool {
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    [*pubkey, r]
+        .into_iter()
+        .all(|point| {
+            CompressedEdwardsY(point)
+                .decompress()
+                .is_some_and(|point| !point.is_small_order() && point.is_torsion_free())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::Encoding;
+    use ed25519_dalek::Verifier;
+
+    // the group order L, little-endian
+    const ORDER: &str = "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+
+    // RFC 8032 section 7.1, tests 1 to 3: (public key, message, signature)
+    const RFC8032: [(&str, &str, &str); 3] = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    // canonical encodings of the eight-torsion points, as in the Zcash/ZIP 215 edge cases
+    const SMALL_ORDER_POINTS: [&str; 8] = [
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000080",
+        "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05",
+        "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc85",
+        "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+        "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+    ];
+
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        Encoding::Hex.decode(hex).unwrap().try_into().unwrap()
+    }
+
+    fn vector(index: usize) -> ([u8; 32], Vec<u8>, [u8; 64]) {
+        let (pubkey, message, signature) = RFC8032[index];
+        (bytes(pubkey), Encoding::Hex.decode(message).unwrap(), bytes(signature))
+    }
+
+    #[test]
+    fn accepts_rfc8032_vectors() {
+        for index in 0..RFC8032.len() {
+            let (pubkey, message, signature) = vector(index);
+            assert_eq!(verify_strict(&pubkey, &message, &signature), Ok(()));
+        }
+    }
+
+    #[test]
+    fn reports_mismatch_for_other_message() {
+        let (pubkey, _, signature) = vector(1);
+        assert_eq!(verify_strict(&pubkey, b"s", &signature), Err(Rejection::Mismatch));
+    }
+
+    #[test]
+    fn rejects_s_plus_order() {
+        // S + L verifies under the unreduced equation, the classic malleability case
+        let (pubkey, message, mut signature) = vector(0);
+        let order: [u8; 32] = bytes(ORDER);
+        let mut carry = 0u16;
+        for (i, byte) in order.iter().enumerate() {
+            let sum = signature[32 + i] as u16 + *byte as u16 + carry;
+            signature[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert_eq!(verify_strict(&pubkey, &message, &signature), Err(Rejection::NonCanonicalS));
+    }
+
+    #[test]
+    fn rejects_s_equal_to_order() {
+        let (pubkey, message, mut signature) = vector(2);
+        signature[32..].copy_from_slice(&bytes::<32>(ORDER));
+        assert_eq!(verify_strict(&pubkey, &message, &signature), Err(Rejection::NonCanonicalS));
+    }
+
+    #[test]
+    fn rejects_small_order_public_keys() {
+        let (_, message, signature) = vector(0);
+        for point in SMALL_ORDER_POINTS {
+            assert_eq!(verify_strict(&bytes(point), &message, &signature), Err(Rejection::SmallOrderPublicKey), "{}", point);
+        }
+    }
+
+    #[test]
+    fn rejects_small_order_r() {
+        let (pubkey, message, _) = vector(0);
+        for point in SMALL_ORDER_POINTS {
+            let mut signature = [0u8; 64];
+            signature[..32].copy_from_slice(&bytes::<32>(point));
+            assert_eq!(verify_strict(&pubkey, &message, &signature), Err(Rejection::SmallOrderR), "{}", point);
+        }
+    }
+
+    #[test]
+    fn small_order_points_are_the_eight_torsion_subgroup() {
+        let points: Vec<_> = SMALL_ORDER_POINTS
+            .iter()
+            .map(|point| CompressedEdwardsY(bytes(point)).decompress().unwrap())
+            .collect();
+        for (i, point) in points.iter().enumerate() {
+            assert!(point.is_small_order(), "{}", SMALL_ORDER_POINTS[i]);
+            assert!(points[..i].iter().all(|other| other != point), "{}", SMALL_ORDER_POINTS[i]);
+        }
+    }
+
+    #[test]
+    fn rejects_identity_signature_that_verify_accepts() {
+        // A = R = identity with S = 0 satisfies the equation for any message
+        let identity = SMALL_ORDER_POINTS[0];
+        let pubkey: [u8; 32] = bytes(identity);
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&pubkey);
+        let lenient = DalekPublicKey::from_bytes(&pubkey)
+            .unwrap()
+            .verify(b"any message", &DalekSignature::from_bytes(&signature).unwrap());
+        assert!(lenient.is_ok());
+        assert_eq!(verify_strict(&pubkey, b"any message", &signature), Err(Rejection::SmallOrderPublicKey));
+    }
+
+    #[test]
+    fn prime_order_rejects_torsion() {
+        for index in 0..RFC8032.len() {
+            let (pubkey, _, signature) = vector(index);
+            assert!(prime_order(&pubkey, &signature));
+        }
+        let (pubkey, _, signature) = vector(0);
+        let torsion = CompressedEdwardsY(bytes(SMALL_ORDER_POINTS[4])).decompress().unwrap();
+        let torsioned = (CompressedEdwardsY(pubkey).decompress().unwrap() + torsion).compress().to_bytes();
+        assert!(!prime_order(&torsioned, &signature));
+        for point in SMALL_ORDER_POINTS {
+            let mut small_r = signature;
+            small_r[..32].copy_from_slice(&bytes::<32>(point));
+            assert!(!prime_order(&bytes(point), &signature), "{}", point);
+            assert!(!prime_order(&pubkey, &small_r), "{}", point);
+        }
+    }
+
+    #[test]
+    fn rejects_r_off_the_curve() {
+        let (pubkey, message, mut signature) = vector(0);
+        // y = 2 has no matching x on the curve
+        signature[..32].copy_from_slice(&bytes::<32>("0200000000000000000000000000000000000000000000000000000000000000"));
+        assert_eq!(verify_strict(&pubkey, &message, &signature), Err(Rejection::InvalidR));
+    }
+}
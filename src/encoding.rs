@@ -0,0 +1,68 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+/// Text encodings accepted and produced for raw bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Base58,
+    Base64,
+    Hex,
+}
+
+impl Encoding {
+    pub fn parse(value: &str) -> Result<Encoding, String> {
+        match value {
+            "base58" => Ok(Encoding::Base58),
+            "base64" => Ok(Encoding::Base64),
+            "hex" => Ok(Encoding::Hex),
+            _ => Err(format!("Unsupported encoding: {}", value)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Base58 => "base58",
+            Encoding::Base64 => "base64",
+            Encoding::Hex => "hex",
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Base58 => bs58::encode(bytes).into_string(),
+            Encoding::Base64 => BASE64.encode(bytes),
+            Encoding::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+        }
+    }
+
+    pub fn decode(&self, value: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Encoding::Base58 => bs58::decode(value).into_vec().map_err(|_| "Invalid base58 data".to_string()),
+            Encoding::Base64 => BASE64.decode(value).map_err(|_| "Invalid base64 data".to_string()),
+            Encoding::Hex => decode_hex(value),
+        }
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    // from_str_radix alone would also take a sign, e.g. "+f"
+    if !value.bytes().all(|b| b.is_ascii_hexdigit()) || !value.len().is_multiple_of(2) {
+        return Err("Invalid hex data".to_string());
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).map_err(|_| "Invalid hex data".to_string()))
+        .collect()
+}
+
+/// Reads an optional encoding field, falling back to `default` when absent or null.
+pub fn parse_encoding_field(req: &serde_json::Value, field: &str, default: Encoding) -> Result<Encoding, String> {
+    match req.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(default),
+        Some(value) => match value.as_str() {
+            Some(value) => Encoding::parse(value),
+            None => Err(format!("Invalid {}", field)),
+        },
+    }
+}
@@ -1,47 +1,101 @@
 use axum::{Json, http::StatusCode};
-use serde_json::json;
 use solana_sdk::signature::{Keypair, Signer};
-use bs58;
 use crate::models::{KeypairResponse, KeypairResponseData};
 use axum::extract::Json as AxumJson;
 use crate::models::{TokenCreateRequest, TokenInstructionResponse, TokenInstructionResponseData, AccountMetaModel, TokenMintRequest};
 use solana_program::pubkey::Pubkey;
-use spl_token::instruction::initialize_mint;
-use spl_token::instruction::mint_to;
-use base64::engine::general_purpose::STANDARD as BASE64;
-use base64::Engine;
+use spl_token_2022::instruction::initialize_mint;
+use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::state::{AccountState, Mint};
+use solana_program::rent::Rent;
+use solana_program::program_pack::Pack;
+use spl_token_2022::instruction::mint_to;
 use crate::models::{MessageSignRequest, MessageSignResponse, MessageSignResponseData};
-use solana_sdk::signature::Signature;
-use crate::models::{MessageVerifyRequest, MessageVerifyResponse, MessageVerifyResponseData};
+use crate::models::{MessageVerifyRequest, MessageVerifyResponse, MessageVerifyResponseData, OffchainMessageModel};
+use crate::models::{MessageSignBatchResponse, MessageSignBatchResponseData};
+use crate::models::{MessageVerifyBatchResponse, MessageVerifyBatchResponseData, MessageVerifyBatchItemModel};
 use crate::models::{SendSolRequest, SendSolResponse, SendSolResponseData};
 use solana_sdk::system_instruction;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::stake::instruction as stake_instruction;
+use solana_sdk::stake::state::{Authorized, Lockup, StakeAuthorize, StakeStateV2};
 use crate::models::{SendTokenRequest, SendTokenResponse, SendTokenResponseData, SendTokenAccountMeta};
-use spl_token::instruction::transfer as spl_transfer;
+use crate::models::{SendBatchResponse, SendBatchResponseData, SendBatchTransactionModel};
+#[allow(deprecated)]
+use spl_token_2022::instruction::transfer as spl_transfer;
+use spl_token_2022::instruction::transfer_checked;
+use spl_token::instruction::{approve, approve_checked, revoke, set_authority, close_account, sync_native, AuthorityType};
+use solana_program::instruction::Instruction;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use crate::models::{TokenApproveRequest, TokenRevokeRequest, TokenAtaResponse, TokenAtaResponseData};
+use crate::models::{WrapSolResponse, WrapSolResponseData};
+use crate::models::{StakeInstructionResponse, StakeInstructionResponseData};
+use crate::models::{DecodeInstructionResponse, DecodedAccountModel, DecodedInstructionModel};
+use crate::models::{TransactionInspectResponse, TransactionInspectResponseData, TransactionSignatureModel, TransactionAccountModel, InspectedInstructionModel};
+use crate::models::{AccountDecodeResponse, AccountDecodeResponseData};
+use crate::models::{Secp256k1KeypairResponse, Secp256k1KeypairResponseData, Secp256k1SignResponse, Secp256k1SignResponseData};
+use crate::models::{SiwsChallengeResponse, SiwsChallengeResponseData, SiwsVerifyResponse, SiwsVerifyResponseData};
+use crate::models::{SiwsSessionResponse, SiwsSessionResponseData};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::message::{Message, VersionedMessage};
+use crate::decoder::{decode_account, decode_instruction};
+use crate::ed25519_strict::{self, Rejection};
+use crate::encoding::{parse_encoding_field, Encoding};
+use crate::offchain::{self, OffchainMessage};
+use crate::precompiles::{new_ed25519_instruction, Ed25519SignatureEntry};
+use crate::precompiles::{eth_address, eth_address_checksummed, generate_secp256k1_secret, new_secp256k1_instruction, secp256k1_recover, secp256k1_sign, EthAddress, Secp256k1SignatureEntry};
+use crate::siws::{self, SiwsMessage, SiwsStore};
+use sha2::{Digest, Sha256};
+use axum::extract::State;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
 use std::str::FromStr;
 use ed25519_dalek::{PublicKey as DalekPublicKey, Signature as DalekSignature, Verifier};
-use spl_associated_token_account;
-
-pub async fn keypair_handler() -> (StatusCode, Json<KeypairResponse>) {
-    match Keypair::new() {
-        keypair => {
-            let pubkey = keypair.pubkey().to_string();
-            let secret = bs58::encode(keypair.to_bytes()).into_string();
-            let data = KeypairResponseData { pubkey, secret };
-            (
-                StatusCode::OK,
-                Json(KeypairResponse {
-                    success: true,
-                    data: Some(data),
-                    error: None,
-                })
-            )
-        }
-    }
+
+// the body is optional; when sent, `encoding` selects how the secret is returned
+pub async fn keypair_handler(req: Option<Json<serde_json::Value>>) -> (StatusCode, Json<KeypairResponse>) {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            Json(KeypairResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let keypair = Keypair::new();
+    let pubkey = keypair.pubkey().to_string();
+    let secret = encoding.encode(&keypair.to_bytes());
+    let data = KeypairResponseData { pubkey, secret };
+    (
+        StatusCode::OK,
+        Json(KeypairResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
 }
 
 pub async fn token_create_handler(
     AxumJson(req): AxumJson<serde_json::Value>,
 ) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let mint = req.get("mint").and_then(|v| v.as_str());
     let mint_authority = req.get("mintAuthority").and_then(|v| v.as_str());
     let decimals = req.get("decimals").and_then(|v| v.as_u64());
@@ -76,17 +130,69 @@ pub async fn token_create_handler(
             })
         );
     }
+    let token_program = match parse_token_program(&req) {
+        Ok(program) => program,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let freeze_authority = match parse_optional_pubkey(&req, "freezeAuthority") {
+        Ok(pk) => pk,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let extensions = match req.get("extensions") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(extensions) => match build_mint_extensions(
+            extensions,
+            &token_program,
+            &Pubkey::from_str(mint.unwrap()).unwrap(),
+            freeze_authority.is_some(),
+        ) {
+            Ok(extensions) => Some(extensions),
+            Err(e) => return (
+                StatusCode::BAD_REQUEST,
+                AxumJson(TokenInstructionResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e),
+                })
+            ),
+        },
+    };
+    let payer = match parse_optional_pubkey(&req, "payer") {
+        Ok(pk) => pk,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let req = TokenCreateRequest {
         mint: mint.unwrap().to_string(),
-        mintAuthority: mint_authority.unwrap().to_string(),
+        mint_authority: mint_authority.unwrap().to_string(),
         decimals: decimals.unwrap() as u8,
     };
     
     let ix = match initialize_mint(
-        &spl_token::id(),
+        &token_program,
         &Pubkey::from_str(&req.mint).unwrap(),
-        &Pubkey::from_str(&req.mintAuthority).unwrap(),
-        None,
+        &Pubkey::from_str(&req.mint_authority).unwrap(),
+        freeze_authority.as_ref(),
         req.decimals,
     ) {
         Ok(ix) => ix,
@@ -104,12 +210,46 @@ pub async fn token_create_handler(
         is_signer: meta.is_signer,
         is_writable: meta.is_writable,
     }).collect();
-    let instruction_data = bs58::encode(ix.data).into_string();
-    let data = TokenInstructionResponseData {
+    let instruction_data = encoding.encode(&ix.data);
+    let mut data = TokenInstructionResponseData {
         program_id: ix.program_id.to_string(),
         accounts,
         instruction_data,
+        instructions: None,
+        account_size: None,
+        rent_exempt_lamports: None,
     };
+    if extensions.is_some() || payer.is_some() {
+        let (extension_types, extension_ixs) = extensions.unwrap_or_default();
+        let space = match ExtensionType::try_calculate_account_len::<Mint>(&extension_types) {
+            Ok(space) => space,
+            Err(e) => return (
+                StatusCode::OK,
+                AxumJson(TokenInstructionResponse {
+                    success: false,
+                    data: None,
+                    error: Some(format!("Failed to size mint account: {}", e)),
+                })
+            ),
+        };
+        let rent = Rent::default().minimum_balance(space);
+        // create_account, then every extension init, then initialize_mint last
+        let mut instructions = Vec::new();
+        if let Some(payer) = payer {
+            instructions.push(token_instruction_data(system_instruction::create_account(
+                &payer,
+                &Pubkey::from_str(&req.mint).unwrap(),
+                rent,
+                space as u64,
+                &token_program,
+            ), encoding));
+        }
+        instructions.extend(extension_ixs.into_iter().map(|ix| token_instruction_data(ix, encoding)));
+        instructions.push(token_instruction_data(ix, encoding));
+        data.instructions = Some(instructions);
+        data.account_size = Some(space);
+        data.rent_exempt_lamports = Some(rent);
+    }
     (
         StatusCode::OK,
         AxumJson(TokenInstructionResponse {
@@ -123,6 +263,17 @@ pub async fn token_create_handler(
 pub async fn token_mint_handler(
     AxumJson(req): AxumJson<serde_json::Value>,
 ) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let mint = req.get("mint").and_then(|v| v.as_str());
     let destination = req.get("destination").and_then(|v| v.as_str());
     let authority = req.get("authority").and_then(|v| v.as_str());
@@ -167,22 +318,59 @@ pub async fn token_mint_handler(
             })
         );
     }
+    let payer = match parse_create_ata(&req, Pubkey::from_str(authority.unwrap()).unwrap()) {
+        Ok(payer) => payer,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let token_program = match parse_token_program(&req) {
+        Ok(program) => program,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenInstructionResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
     let req = TokenMintRequest {
         mint: mint.unwrap().to_string(),
         destination: destination.unwrap().to_string(),
         authority: authority.unwrap().to_string(),
         amount: amount.unwrap(),
+        create_ata: payer.is_some(),
+        payer: payer.map(|pk| pk.to_string()),
     };
-    let destination_ata = spl_associated_token_account::get_associated_token_address(
+    let destination_ata = get_associated_token_address_with_program_id(
         &Pubkey::from_str(&req.destination).unwrap(),
         &Pubkey::from_str(&req.mint).unwrap(),
+        &token_program,
     );
     let ix = match mint_to(
-        &spl_token::id(),
+        &token_program,
         &Pubkey::from_str(&req.mint).unwrap(),
         &destination_ata,
         &Pubkey::from_str(&req.authority).unwrap(),
-        &[],
+        &signer_refs,
         req.amount,
     ) {
         Ok(ix) => ix,
@@ -195,17 +383,16 @@ pub async fn token_mint_handler(
             })
         ),
     };
-    let accounts: Vec<AccountMetaModel> = ix.accounts.iter().map(|meta| AccountMetaModel {
-        pubkey: meta.pubkey.to_string(),
-        is_signer: meta.is_signer,
-        is_writable: meta.is_writable,
-    }).collect();
-    let instruction_data = bs58::encode(ix.data).into_string();
-    let data = TokenInstructionResponseData {
-        program_id: ix.program_id.to_string(),
-        accounts,
-        instruction_data,
-    };
+    let mut data = token_instruction_data(ix.clone(), encoding);
+    if req.create_ata {
+        let create_ix = create_associated_token_account_idempotent(
+            &Pubkey::from_str(req.payer.as_ref().unwrap()).unwrap(),
+            &Pubkey::from_str(&req.destination).unwrap(),
+            &Pubkey::from_str(&req.mint).unwrap(),
+            &token_program,
+        );
+        data.instructions = Some(vec![token_instruction_data(create_ix, encoding), token_instruction_data(ix, encoding)]);
+    }
     (
         StatusCode::OK,
         AxumJson(TokenInstructionResponse {
@@ -219,6 +406,28 @@ pub async fn token_mint_handler(
 pub async fn message_sign_handler(
     AxumJson(req): AxumJson<serde_json::Value>,
 ) -> (StatusCode, AxumJson<MessageSignResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageSignResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageSignResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let message = req.get("message").and_then(|v| v.as_str());
     let secret = req.get("secret").and_then(|v| v.as_str());
     if message.is_none() || secret.is_none() {
@@ -254,17 +463,51 @@ pub async fn message_sign_handler(
             })
         ),
     };
+    let offchain = match parse_offchain_options(&req, &keypair.pubkey()) {
+        Ok(offchain) => offchain,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageSignResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let req = MessageSignRequest {
         message: message.unwrap().to_string(),
-        secret: secret.unwrap().to_string(),
     };
-    let message_bytes = req.message.as_bytes();
-    let signature = keypair.sign_message(message_bytes);
-    let signature_b58 = bs58::encode(signature.as_ref()).into_string();
+    let message_bytes = match decode_message(&req.message, message_encoding) {
+        Ok(bytes) => bytes,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageSignResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let offchain = match offchain.map(|header| OffchainMessage::new(header, message_bytes.clone())).transpose() {
+        Ok(offchain) => offchain,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageSignResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let message_bytes = offchain.as_ref().map(|offchain| offchain.serialize()).unwrap_or(message_bytes);
+    let signature = keypair.sign_message(&message_bytes);
     let data = MessageSignResponseData {
-        signature: signature_b58,
+        signature: encoding.encode(signature.as_ref()),
         pubkey: keypair.pubkey().to_string(),
         message: req.message,
+        message_length: message_bytes.len(),
+        message_sha256: Encoding::Hex.encode(&Sha256::digest(&message_bytes)),
+        offchain: offchain.map(|offchain| offchain_message_model(&offchain, &message_bytes, encoding)),
     };
     (
         StatusCode::OK,
@@ -279,6 +522,39 @@ pub async fn message_sign_handler(
 pub async fn message_verify_handler(
     AxumJson(req): AxumJson<serde_json::Value>,
 ) -> (StatusCode, AxumJson<MessageVerifyResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let strict = match parse_flag(&req, "strict") {
+        Ok(strict) => strict,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let message = req.get("message").and_then(|v| v.as_str());
     let signature_str = req.get("signature").and_then(|v| v.as_str());
     let pubkey = req.get("pubkey").and_then(|v| v.as_str());
@@ -304,15 +580,15 @@ pub async fn message_verify_handler(
             })
         ),
     };
-    // validate base58 signature realisation 1
-    let signature_bytes = match bs58::decode(signature_str.unwrap()).into_vec() {
+    // validate signature in the requested encoding realisation 1
+    let signature_bytes = match encoding.decode(signature_str.unwrap()) {
         Ok(bytes) => bytes,
         Err(_) => return (
             StatusCode::BAD_REQUEST,
             AxumJson(MessageVerifyResponse {
                 success: false,
                 data: None,
-                error: Some("Invalid base58 signature".to_string()),
+                error: Some(format!("Invalid {} signature", encoding.name())),
             })
         ),
     };
@@ -326,8 +602,10 @@ pub async fn message_verify_handler(
             })
         );
     }
+    // strict mode reports an out-of-range S as a rejection instead of a malformed signature
     let dalek_signature = match DalekSignature::from_bytes(&signature_bytes) {
-        Ok(sig) => sig,
+        Ok(sig) => Some(sig),
+        Err(_) if strict => None,
         Err(_) => return (
             StatusCode::BAD_REQUEST,
             AxumJson(MessageVerifyResponse {
@@ -349,16 +627,61 @@ pub async fn message_verify_handler(
             })
         ),
     };
+    let offchain = match parse_offchain_options(&req, &pubkey_val) {
+        Ok(offchain) => offchain,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let req = MessageVerifyRequest {
         message: message.unwrap().to_string(),
-        signature: signature_str.unwrap().to_string(),
         pubkey: pubkey.unwrap().to_string(),
     };
-    let valid = dalek_pubkey.verify(req.message.as_bytes(), &dalek_signature).is_ok();
+    let message_bytes = match decode_message(&req.message, message_encoding) {
+        Ok(bytes) => bytes,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let offchain = match offchain.map(|header| OffchainMessage::new(header, message_bytes.clone())).transpose() {
+        Ok(offchain) => offchain,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(MessageVerifyResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let message_bytes = offchain.as_ref().map(|offchain| offchain.serialize()).unwrap_or(message_bytes);
+    let outcome = match dalek_signature {
+        Some(dalek_signature) if !strict => dalek_pubkey.verify(&message_bytes, &dalek_signature).map_err(|_| Rejection::Mismatch),
+        _ => {
+            let mut signature = [0u8; 64];
+            signature.copy_from_slice(&signature_bytes);
+            ed25519_strict::verify_strict(&pubkey_bytes, &message_bytes, &signature)
+        }
+    };
     let data = MessageVerifyResponseData {
-        valid,
+        valid: outcome.is_ok(),
+        strict,
+        reason: outcome.err().map(|rejection| rejection.name().to_string()),
         message: req.message,
         pubkey: req.pubkey,
+        message_length: message_bytes.len(),
+        message_sha256: Encoding::Hex.encode(&Sha256::digest(&message_bytes)),
+        offchain: offchain.map(|offchain| offchain_message_model(&offchain, &message_bytes, encoding)),
     };
     (
         StatusCode::OK,
@@ -370,53 +693,307 @@ pub async fn message_verify_handler(
     )
 }
 
-pub async fn send_sol_handler(
-    AxumJson(req): AxumJson<serde_json::Value>,
-) -> (StatusCode, AxumJson<SendSolResponse>) {
-    let from = req.get("from").and_then(|v| v.as_str());
-    let to = req.get("to").and_then(|v| v.as_str());
-    let lamports = match req.get("lamports").and_then(|v| v.as_u64()) {
-        Some(0) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                AxumJson(SendSolResponse {
-                    success: false,
-                    data: None,
-                    error: Some("Amount must be greater than 0".to_string()),
-                })
-            );
+// utf8 (the default) is reported as None, messages are then signed as text
+fn parse_message_encoding(req: &serde_json::Value) -> Result<Option<Encoding>, String> {
+    parse_text_encoding(req, "messageEncoding")
+}
+
+fn parse_text_encoding(req: &serde_json::Value, field: &str) -> Result<Option<Encoding>, String> {
+    match req.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_str() {
+            Some("utf8") => Ok(None),
+            Some(value) => Encoding::parse(value).map(Some),
+            None => Err(format!("Invalid {}", field)),
         },
-        Some(val) => val,
-        None => {
-            return (
-                StatusCode::BAD_REQUEST,
-                AxumJson(SendSolResponse {
-                    success: false,
-                    data: None,
-                    error: Some("Missing required fields".to_string()),
-                })
-            );
+    }
+}
+
+// messages are UTF-8 text unless `messageEncoding` names a binary encoding
+fn decode_message(message: &str, encoding: Option<Encoding>) -> Result<Vec<u8>, String> {
+    match encoding {
+        None => Ok(message.as_bytes().to_vec()),
+        Some(encoding) => encoding.decode(message).map_err(|_| format!("Invalid {} message", encoding.name())),
+    }
+}
+
+// `offchain` is `true` or `{ header, applicationDomain, signers }`; the header defaults to the SDK's legacy one,
+// and with the extended header the signing key defaults to the only signer
+fn parse_offchain_options(req: &serde_json::Value, signer: &Pubkey) -> Result<Option<offchain::Header>, String> {
+    let options = match req.get("offchain") {
+        None | Some(serde_json::Value::Null) | Some(serde_json::Value::Bool(false)) => return Ok(None),
+        Some(serde_json::Value::Bool(true)) => &serde_json::Value::Null,
+        Some(options @ serde_json::Value::Object(_)) => options,
+        Some(_) => return Err("Invalid offchain".to_string()),
+    };
+    match options.get("header").map(|v| v.as_str()) {
+        Some(Some("extended")) => {}
+        None | Some(Some("legacy")) if options.get("applicationDomain").is_some() || options.get("signers").is_some() => {
+            return Err("applicationDomain and signers need the extended header".to_string());
         }
+        None => return Ok(Some(offchain::Header::default())),
+        Some(Some("legacy")) => return Ok(Some(offchain::Header::Legacy)),
+        Some(_) => return Err("header must be legacy or extended".to_string()),
+    }
+    let application_domain = parse_optional_pubkey(options, "applicationDomain")?.unwrap_or_default();
+    let signers = match options.get("signers") {
+        None | Some(serde_json::Value::Null) => vec![*signer],
+        Some(serde_json::Value::Array(values)) => values
+            .iter()
+            .map(|value| value.as_str().ok_or_else(|| "Invalid signer pubkey".to_string()).and_then(|v| parse_pubkey(v, "signer")))
+            .collect::<Result<Vec<Pubkey>, String>>()?,
+        Some(_) => return Err("signers must be an array".to_string()),
     };
-    if from.is_none() || to.is_none() {
-        return (
-            StatusCode::BAD_REQUEST,
-            AxumJson(SendSolResponse {
-                success: false,
-                data: None,
-                error: Some("Missing required fields".to_string()),
-            })
-        );
+    if !signers.contains(signer) {
+        return Err(format!("{} is not one of the off-chain message signers", signer));
     }
-    if Pubkey::from_str(from.unwrap()).is_err() {
-        return (
-            StatusCode::BAD_REQUEST,
-            AxumJson(SendSolResponse {
-                success: false,
-                data: None,
-                error: Some("Invalid sender public key".to_string()),
-            })
-        );
+    Ok(Some(offchain::Header::Extended { application_domain, signers }))
+}
+
+fn offchain_message_model(message: &OffchainMessage, serialized: &[u8], encoding: Encoding) -> OffchainMessageModel {
+    let (application_domain, signers) = match &message.header {
+        offchain::Header::Legacy => (None, None),
+        offchain::Header::Extended { application_domain, signers } => (
+            Some(application_domain.to_string()),
+            Some(signers.iter().map(|signer| signer.to_string()).collect()),
+        ),
+    };
+    OffchainMessageModel {
+        version: offchain::HEADER_VERSION,
+        header: message.header.name().to_string(),
+        application_domain,
+        format: offchain::format_name(message.format).to_string(),
+        signers,
+        serialized_message: encoding.encode(serialized),
+    }
+}
+
+const MAX_BATCH_ITEMS: usize = 10_000;
+
+type VerifyItem = (Vec<u8>, DalekSignature, DalekPublicKey);
+
+fn verify_batch_error(error: String) -> (StatusCode, AxumJson<MessageVerifyBatchResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(MessageVerifyBatchResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+// same checks and messages as /message/verify, per entry
+fn parse_verify_item(
+    item: &serde_json::Value,
+    encoding: Encoding,
+    message_encoding: Option<Encoding>,
+) -> Result<VerifyItem, String> {
+    let message = item.get("message").and_then(|v| v.as_str());
+    let signature = item.get("signature").and_then(|v| v.as_str());
+    let pubkey = item.get("pubkey").and_then(|v| v.as_str());
+    if message.is_none() || signature.is_none() || pubkey.is_none() {
+        return Err("Missing required fields".to_string());
+    }
+    let pubkey = Pubkey::from_str(pubkey.unwrap()).map_err(|_| "Invalid pubkey".to_string())?;
+    let signature = encoding.decode(signature.unwrap()).map_err(|_| format!("Invalid {} signature", encoding.name()))?;
+    if signature.len() != 64 {
+        return Err("Signature must be 64 bytes".to_string());
+    }
+    let signature = DalekSignature::from_bytes(&signature).map_err(|_| "Invalid signature bytes".to_string())?;
+    let pubkey = DalekPublicKey::from_bytes(&pubkey.to_bytes()).map_err(|_| "Invalid public key bytes for ed25519".to_string())?;
+    Ok((decode_message(message.unwrap(), message_encoding)?, signature, pubkey))
+}
+
+pub async fn message_verify_batch_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<MessageVerifyBatchResponse>) {
+    // a bare array is accepted as well as `{ "items": [...] }`
+    let items = match req.as_array().or_else(|| req.get("items").and_then(|v| v.as_array())) {
+        Some(items) => items,
+        None => return verify_batch_error("Missing required fields".to_string()),
+    };
+    if items.is_empty() || items.len() > MAX_BATCH_ITEMS {
+        return verify_batch_error(format!("Between 1 and {} items are allowed", MAX_BATCH_ITEMS));
+    }
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return verify_batch_error(e),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return verify_batch_error(e),
+    };
+    let parsed: Vec<Result<VerifyItem, String>> = items
+        .iter()
+        .map(|item| parse_verify_item(item, encoding, message_encoding))
+        .collect();
+    // only prime-order keys and R points go into the batch, where it agrees with `verify`
+    let batchable: Vec<bool> = parsed
+        .iter()
+        .map(|item| {
+            item.as_ref()
+                .is_ok_and(|(_, signature, pubkey)| ed25519_strict::prime_order(pubkey.as_bytes(), &signature.to_bytes()))
+        })
+        .collect();
+    let batch: Vec<&VerifyItem> = parsed
+        .iter()
+        .zip(&batchable)
+        .filter_map(|(item, batchable)| item.as_ref().ok().filter(|_| *batchable))
+        .collect();
+    let messages: Vec<&[u8]> = batch.iter().map(|(message, _, _)| message.as_slice()).collect();
+    let signatures: Vec<DalekSignature> = batch.iter().map(|(_, signature, _)| *signature).collect();
+    let pubkeys: Vec<DalekPublicKey> = batch.iter().map(|(_, _, pubkey)| *pubkey).collect();
+    // one batch check covers the common all-valid case, any failure falls back to checking each entry
+    let batch_valid = !batch.is_empty() && ed25519_dalek::verify_batch(&messages, &signatures, &pubkeys).is_ok();
+    let results: Vec<MessageVerifyBatchItemModel> = parsed
+        .into_iter()
+        .zip(batchable)
+        .map(|(item, batchable)| match item {
+            Ok(_) if batchable && batch_valid => MessageVerifyBatchItemModel { valid: true, error: None },
+            Ok((message, signature, pubkey)) => match pubkey.verify(&message, &signature) {
+                Ok(()) => MessageVerifyBatchItemModel { valid: true, error: None },
+                Err(_) => MessageVerifyBatchItemModel { valid: false, error: Some("Signature verification failed".to_string()) },
+            },
+            Err(e) => MessageVerifyBatchItemModel { valid: false, error: Some(e) },
+        })
+        .collect();
+    let valid_count = results.iter().filter(|result| result.valid).count();
+    let data = MessageVerifyBatchResponseData {
+        all_valid: valid_count == results.len(),
+        valid_count,
+        results,
+    };
+    (
+        StatusCode::OK,
+        AxumJson(MessageVerifyBatchResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn sign_batch_error(error: String) -> (StatusCode, AxumJson<MessageSignBatchResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(MessageSignBatchResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn message_sign_batch_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<MessageSignBatchResponse>) {
+    let messages = req.get("messages").and_then(|v| v.as_array());
+    let secret = req.get("secret").and_then(|v| v.as_str());
+    if messages.is_none() || secret.is_none() {
+        return sign_batch_error("Missing required fields".to_string());
+    }
+    let messages = messages.unwrap();
+    if messages.is_empty() || messages.len() > MAX_BATCH_ITEMS {
+        return sign_batch_error(format!("Between 1 and {} messages are allowed", MAX_BATCH_ITEMS));
+    }
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return sign_batch_error(e),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return sign_batch_error(e),
+    };
+    // the secret is decoded once for the whole batch
+    let secret_bytes = match bs58::decode(secret.unwrap()).into_vec() {
+        Ok(bytes) => bytes,
+        Err(_) => return sign_batch_error("Invalid base58 secret key".to_string()),
+    };
+    let keypair = match Keypair::from_bytes(&secret_bytes) {
+        Ok(kp) => kp,
+        Err(_) => return sign_batch_error("Invalid secret key bytes".to_string()),
+    };
+    let mut signatures = Vec::with_capacity(messages.len());
+    for (index, message) in messages.iter().enumerate() {
+        let message_bytes = match message.as_str().ok_or_else(|| "Invalid message".to_string()).and_then(|m| decode_message(m, message_encoding)) {
+            Ok(bytes) => bytes,
+            Err(e) => return sign_batch_error(format!("{} at index {}", e, index)),
+        };
+        signatures.push(encoding.encode(keypair.sign_message(&message_bytes).as_ref()));
+    }
+    let data = MessageSignBatchResponseData {
+        pubkey: keypair.pubkey().to_string(),
+        signatures,
+    };
+    (
+        StatusCode::OK,
+        AxumJson(MessageSignBatchResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+pub async fn send_sol_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<SendSolResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendSolResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let from = req.get("from").and_then(|v| v.as_str());
+    let to = req.get("to").and_then(|v| v.as_str());
+    let lamports = match req.get("lamports").and_then(|v| v.as_u64()) {
+        Some(0) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                AxumJson(SendSolResponse {
+                    success: false,
+                    data: None,
+                    error: Some("Amount must be greater than 0".to_string()),
+                })
+            );
+        },
+        Some(val) => val,
+        None => {
+            return (
+                StatusCode::BAD_REQUEST,
+                AxumJson(SendSolResponse {
+                    success: false,
+                    data: None,
+                    error: Some("Missing required fields".to_string()),
+                })
+            );
+        }
+    };
+    if from.is_none() || to.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendSolResponse {
+                success: false,
+                data: None,
+                error: Some("Missing required fields".to_string()),
+            })
+        );
+    }
+    if Pubkey::from_str(from.unwrap()).is_err() {
+        return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendSolResponse {
+                success: false,
+                data: None,
+                error: Some("Invalid sender public key".to_string()),
+            })
+        );
     }
     if Pubkey::from_str(to.unwrap()).is_err() {
         return (
@@ -428,18 +1005,63 @@ pub async fn send_sol_handler(
             })
         );
     }
+    let priority_fee = match parse_priority_fee(&req) {
+        Ok(instructions) => instructions,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendSolResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let memo = match parse_memo(&req) {
+        Ok(memo) => memo,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendSolResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let req = SendSolRequest {
         from: from.unwrap().to_string(),
         to: to.unwrap().to_string(),
         lamports,
+        memo: memo.map(|memo| String::from_utf8(memo).unwrap()),
     };
     let ix = system_instruction::transfer(&Pubkey::from_str(&req.from).unwrap(), &Pubkey::from_str(&req.to).unwrap(), req.lamports);
     let accounts = ix.accounts.iter().map(|meta| meta.pubkey.to_string()).collect();
-    let instruction_data = bs58::encode(ix.data).into_string();
+    let instruction_data = encoding.encode(&ix.data);
+    // compute budget first, then the transfer, then the memo
+    let mut all = priority_fee;
+    all.push(ix.clone());
+    if let Some(memo) = &req.memo {
+        all.push(spl_memo::build_memo(memo.as_bytes(), &[]));
+        if let Err(e) = check_memo_fits(&all, &ix.accounts[0].pubkey) {
+            return (
+                StatusCode::BAD_REQUEST,
+                AxumJson(SendSolResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e),
+                })
+            );
+        }
+    }
+    let instructions = if all.len() > 1 {
+        Some(all.into_iter().map(|ix| token_instruction_data(ix, encoding)).collect())
+    } else {
+        None
+    };
     let data = SendSolResponseData {
         program_id: ix.program_id.to_string(),
         accounts,
         instruction_data,
+        instructions,
     };
     (
         StatusCode::OK,
@@ -454,6 +1076,17 @@ pub async fn send_sol_handler(
 pub async fn send_token_handler(
     AxumJson(req): AxumJson<serde_json::Value>,
 ) -> (StatusCode, AxumJson<SendTokenResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
     let destination = req.get("destination").and_then(|v| v.as_str());
     let mint = req.get("mint").and_then(|v| v.as_str());
     let owner = req.get("owner").and_then(|v| v.as_str());
@@ -520,28 +1153,132 @@ pub async fn send_token_handler(
             })
         );
     }
+    // an optional delegate signs the transfer in place of the owner
+    let delegate = match parse_optional_pubkey(&req, "delegate") {
+        Ok(delegate) => delegate,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let decimals = match parse_decimals(&req) {
+        Ok(decimals) => decimals,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    // strict mode refuses to fall back to the unchecked transfer
+    let strict = match parse_flag(&req, "strict") {
+        Ok(strict) => strict,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    if strict && decimals.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some("decimals is required in strict mode".to_string()),
+            })
+        );
+    }
+    let payer = match parse_create_ata(&req, Pubkey::from_str(owner.unwrap()).unwrap()) {
+        Ok(payer) => payer,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let token_program = match parse_token_program(&req) {
+        Ok(program) => program,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let priority_fee = match parse_priority_fee(&req) {
+        Ok(instructions) => instructions,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let memo = match parse_memo(&req) {
+        Ok(memo) => memo,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(SendTokenResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
     let req = SendTokenRequest {
         destination: destination.unwrap().to_string(),
         mint: mint.unwrap().to_string(),
         owner: owner.unwrap().to_string(),
         amount,
+        delegate: delegate.map(|pk| pk.to_string()),
+        decimals,
+        create_ata: payer.is_some(),
+        payer: payer.map(|pk| pk.to_string()),
+        memo: memo.map(|memo| String::from_utf8(memo).unwrap()),
     };
-    let source_ata = spl_associated_token_account::get_associated_token_address(
-        &Pubkey::from_str(&req.owner).unwrap(),
-        &Pubkey::from_str(&req.mint).unwrap(),
-    );
-    let destination_ata = spl_associated_token_account::get_associated_token_address(
-        &Pubkey::from_str(&req.destination).unwrap(),
-        &Pubkey::from_str(&req.mint).unwrap(),
-    );
-    let ix = match spl_transfer(
-        &spl_token::id(),
-        &source_ata,
-        &destination_ata,
-        &Pubkey::from_str(&req.owner).unwrap(),
-        &[],
-        req.amount,
-    ) {
+    let owner = Pubkey::from_str(&req.owner).unwrap();
+    let transfer = TokenTransfer {
+        mint: Pubkey::from_str(&req.mint).unwrap(),
+        owner,
+        destination: Pubkey::from_str(&req.destination).unwrap(),
+        authority: req.delegate.as_ref().map_or(owner, |delegate| Pubkey::from_str(delegate).unwrap()),
+        amount: req.amount,
+        decimals: req.decimals,
+    };
+    let authority = transfer.authority;
+    let mint = transfer.mint;
+    let destination_ata = get_associated_token_address_with_program_id(&transfer.destination, &mint, &token_program);
+    let (instruction, ix) = token_transfer_instruction(&transfer, &token_program, &signer_refs);
+    let ix = match ix {
         Ok(ix) => ix,
         Err(e) => return (
             StatusCode::OK,
@@ -552,16 +1289,54 @@ pub async fn send_token_handler(
             })
         ),
     };
-    let accounts = vec![
-        SendTokenAccountMeta { pubkey: req.owner.clone(), isSigner: false },
-        SendTokenAccountMeta { pubkey: destination_ata.to_string(), isSigner: false },
-        SendTokenAccountMeta { pubkey: req.owner.clone(), isSigner: false },
+    let mut accounts = vec![
+        SendTokenAccountMeta { pubkey: req.owner.clone(), is_signer: false },
+        SendTokenAccountMeta { pubkey: destination_ata.to_string(), is_signer: false },
+        SendTokenAccountMeta { pubkey: authority.to_string(), is_signer: false },
     ];
-    let instruction_data = bs58::encode(ix.data).into_string();
+    accounts.extend(signers.iter().map(|signer| SendTokenAccountMeta { pubkey: signer.to_string(), is_signer: true }));
+    if req.decimals.is_some() {
+        // transfer_checked carries the mint between source and destination
+        accounts.insert(1, SendTokenAccountMeta { pubkey: mint.to_string(), is_signer: false });
+    }
+    // compute budget first, then the destination ATA, the transfer itself and the memo
+    let mut all = priority_fee;
+    if req.create_ata {
+        all.push(create_associated_token_account_idempotent(
+            &Pubkey::from_str(req.payer.as_ref().unwrap()).unwrap(),
+            &Pubkey::from_str(&req.destination).unwrap(),
+            &mint,
+            &token_program,
+        ));
+    }
+    all.push(ix.clone());
+    if let Some(memo) = &req.memo {
+        all.push(spl_memo::build_memo(memo.as_bytes(), &[]));
+        // the ATA payer covers the fee when there is one, otherwise the transfer authority
+        let fee_payer = req.payer.as_ref().map_or(authority, |payer| Pubkey::from_str(payer).unwrap());
+        if let Err(e) = check_memo_fits(&all, &fee_payer) {
+            return (
+                StatusCode::BAD_REQUEST,
+                AxumJson(SendTokenResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e),
+                })
+            );
+        }
+    }
+    let instructions = if all.len() > 1 {
+        Some(all.into_iter().map(|ix| token_instruction_data(ix, encoding)).collect())
+    } else {
+        None
+    };
+    let instruction_data = encoding.encode(&ix.data);
     let data = SendTokenResponseData {
         program_id: ix.program_id.to_string(),
         accounts,
         instruction_data,
+        instruction: instruction.to_string(),
+        instructions,
     };
     (
         StatusCode::OK,
@@ -571,4 +1346,2038 @@ pub async fn send_token_handler(
             error: None,
         })
     )
-} 
\ No newline at end of file
+}
+
+fn send_batch_error(error: String) -> (StatusCode, AxumJson<SendBatchResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(SendBatchResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+// rough compute unit costs, used to keep each payout transaction under its unit limit
+const SOL_TRANSFER_UNITS: u64 = 150;
+const TOKEN_TRANSFER_UNITS: u64 = 6_500;
+const CREATE_ATA_UNITS: u64 = 30_000;
+const MEMO_UNITS: u64 = 15_000;
+const COMPUTE_BUDGET_UNITS: u64 = 150;
+// without SetComputeUnitLimit the runtime grants this much per instruction other than compute budget ones
+const DEFAULT_INSTRUCTION_UNITS: u64 = 200_000;
+
+fn parse_payout(index: usize, value: &serde_json::Value) -> Result<(Pubkey, u64), String> {
+    let recipient = value.get("recipient").and_then(|v| v.as_str());
+    match (recipient, value.get("amount").and_then(|v| v.as_u64())) {
+        (Some(_), Some(0)) => Err(format!("Amount must be greater than 0 at index {}", index)),
+        (Some(recipient), Some(amount)) => parse_pubkey(recipient, "recipient")
+            .map(|recipient| (recipient, amount))
+            .map_err(|e| format!("{} at index {}", e, index)),
+        _ => Err(format!("Missing required fields at index {}", index)),
+    }
+}
+
+// legacy transaction size with every required signature slot filled
+fn transaction_size(instructions: &[Instruction], fee_payer: &Pubkey) -> usize {
+    let tx = Transaction::new_unsigned(Message::new(instructions, Some(fee_payer)));
+    bincode::serialized_size(&tx).map(|size| size as usize).unwrap_or(usize::MAX)
+}
+
+struct PayoutTransaction {
+    recipients: Vec<usize>,
+    transfers: Vec<Instruction>,
+    compute_units: u64,
+}
+
+pub async fn send_batch_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<SendBatchResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return send_batch_error(e),
+    };
+    let from = match required_pubkey(&req, "from") {
+        Ok(pk) => pk,
+        Err(e) => return send_batch_error(e),
+    };
+    let values = match req.get("recipients") {
+        Some(serde_json::Value::Array(values)) => values,
+        Some(_) => return send_batch_error("recipients must be an array".to_string()),
+        None => return send_batch_error("Missing required fields".to_string()),
+    };
+    if values.is_empty() || values.len() > MAX_BATCH_ITEMS {
+        return send_batch_error(format!("Between 1 and {} recipients are allowed", MAX_BATCH_ITEMS));
+    }
+    let payouts = match values.iter().enumerate().map(|(index, value)| parse_payout(index, value)).collect::<Result<Vec<_>, _>>() {
+        Ok(payouts) => payouts,
+        Err(e) => return send_batch_error(e),
+    };
+    // without a mint the batch pays SOL, otherwise mint, decimals, tokenProgram and createAta work as on /send/token
+    let mint = match parse_optional_pubkey(&req, "mint") {
+        Ok(mint) => mint,
+        Err(e) => return send_batch_error(e),
+    };
+    let decimals = match parse_decimals(&req) {
+        Ok(decimals) => decimals,
+        Err(e) => return send_batch_error(e),
+    };
+    let token_program = match parse_token_program(&req) {
+        Ok(program) => program,
+        Err(e) => return send_batch_error(e),
+    };
+    let payer = match parse_create_ata(&req, from) {
+        Ok(payer) => payer,
+        Err(e) => return send_batch_error(e),
+    };
+    // the compute budget instructions and the memo are repeated in every transaction
+    let priority_fee = match parse_priority_fee(&req) {
+        Ok(instructions) => instructions,
+        Err(e) => return send_batch_error(e),
+    };
+    let memo = match parse_memo(&req) {
+        Ok(memo) => memo.map(|memo| spl_memo::build_memo(&memo, &[])),
+        Err(e) => return send_batch_error(e),
+    };
+    let explicit_limit = req.get("priorityFee")
+        .and_then(|fee| fee.get("computeUnitLimit"))
+        .and_then(|v| v.as_u64());
+    let fixed_units = priority_fee.len() as u64 * COMPUTE_BUDGET_UNITS + memo.as_ref().map_or(0, |_| MEMO_UNITS);
+    let assemble = |transfers: &[Instruction]| -> Vec<Instruction> {
+        priority_fee.iter().chain(transfers).chain(memo.as_ref()).cloned().collect()
+    };
+    let unit_limit = |transfers: &[Instruction]| {
+        explicit_limit.unwrap_or_else(|| {
+            let instructions = transfers.len() as u64 + memo.as_ref().map_or(0, |_| 1);
+            (instructions * DEFAULT_INSTRUCTION_UNITS).min(MAX_COMPUTE_UNIT_LIMIT as u64)
+        })
+    };
+    let fits = |transfers: &[Instruction], units: u64| {
+        fixed_units + units <= unit_limit(transfers) && transaction_size(&assemble(transfers), &from) <= PACKET_DATA_SIZE
+    };
+
+    let mut transactions: Vec<PayoutTransaction> = Vec::new();
+    let mut current = PayoutTransaction { recipients: vec![], transfers: vec![], compute_units: 0 };
+    for (index, (recipient, amount)) in payouts.into_iter().enumerate() {
+        let (group, units) = match mint {
+            None => (vec![system_instruction::transfer(&from, &recipient, amount)], SOL_TRANSFER_UNITS),
+            Some(mint) => {
+                let transfer = TokenTransfer { mint, owner: from, destination: recipient, authority: from, amount, decimals };
+                let ix = match token_transfer_instruction(&transfer, &token_program, &[]).1 {
+                    Ok(ix) => ix,
+                    Err(e) => return (
+                        StatusCode::OK,
+                        AxumJson(SendBatchResponse {
+                            success: false,
+                            data: None,
+                            error: Some(format!("Failed to create instruction: {}", e)),
+                        })
+                    ),
+                };
+                match payer {
+                    Some(payer) => (
+                        vec![create_associated_token_account_idempotent(&payer, &recipient, &mint, &token_program), ix],
+                        CREATE_ATA_UNITS + TOKEN_TRANSFER_UNITS,
+                    ),
+                    None => (vec![ix], TOKEN_TRANSFER_UNITS),
+                }
+            }
+        };
+        let mut candidate = current.transfers.clone();
+        candidate.extend(group.iter().cloned());
+        if fits(&candidate, current.compute_units + units) {
+            current.recipients.push(index);
+            current.transfers = candidate;
+            current.compute_units += units;
+            continue;
+        }
+        if current.recipients.is_empty() || !fits(&group, units) {
+            return send_batch_error(format!("Transfer at index {} does not fit in a single transaction", index));
+        }
+        transactions.push(std::mem::replace(
+            &mut current,
+            PayoutTransaction { recipients: vec![index], transfers: group, compute_units: units },
+        ));
+    }
+    transactions.push(current);
+
+    let data = SendBatchResponseData {
+        fee_payer: from.to_string(),
+        transfer_count: values.len(),
+        transactions: transactions.into_iter().map(|tx| {
+            let instructions = assemble(&tx.transfers);
+            SendBatchTransactionModel {
+                recipients: tx.recipients,
+                size: transaction_size(&instructions, &from),
+                compute_units: fixed_units + tx.compute_units,
+                instructions: instructions.into_iter().map(|ix| token_instruction_data(ix, encoding)).collect(),
+            }
+        }).collect(),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(SendBatchResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+/// A token transfer from the owner's ATA to the destination's ATA.
+struct TokenTransfer {
+    mint: Pubkey,
+    owner: Pubkey,
+    destination: Pubkey,
+    /// Signs for the source account: the owner or its delegate
+    authority: Pubkey,
+    amount: u64,
+    decimals: Option<u8>,
+}
+
+/// The transfer `/send/token` and `/send/batch` emit between the owner's and the destination's
+/// ATAs: transfer_checked when decimals are known, the legacy transfer otherwise.
+fn token_transfer_instruction(
+    transfer: &TokenTransfer,
+    token_program: &Pubkey,
+    signers: &[&Pubkey],
+) -> (&'static str, Result<Instruction, solana_program::program_error::ProgramError>) {
+    let source_ata = get_associated_token_address_with_program_id(&transfer.owner, &transfer.mint, token_program);
+    let destination_ata = get_associated_token_address_with_program_id(&transfer.destination, &transfer.mint, token_program);
+    match transfer.decimals {
+        Some(decimals) => ("transfer_checked", transfer_checked(
+            token_program,
+            &source_ata,
+            &transfer.mint,
+            &destination_ata,
+            &transfer.authority,
+            signers,
+            transfer.amount,
+            decimals,
+        )),
+        // the unchecked variant is still what legacy callers without decimals get
+        #[allow(deprecated)]
+        None => ("transfer", spl_transfer(
+            token_program,
+            &source_ata,
+            &destination_ata,
+            &transfer.authority,
+            signers,
+            transfer.amount,
+        )),
+    }
+}
+
+fn token_instruction_error(status: StatusCode, error: String) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    (
+        status,
+        AxumJson(TokenInstructionResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+fn token_instruction_data(ix: Instruction, encoding: Encoding) -> TokenInstructionResponseData {
+    let accounts: Vec<AccountMetaModel> = ix.accounts.iter().map(|meta| AccountMetaModel {
+        pubkey: meta.pubkey.to_string(),
+        is_signer: meta.is_signer,
+        is_writable: meta.is_writable,
+    }).collect();
+    TokenInstructionResponseData {
+        program_id: ix.program_id.to_string(),
+        accounts,
+        instruction_data: encoding.encode(&ix.data),
+        instructions: None,
+        account_size: None,
+        rent_exempt_lamports: None,
+    }
+}
+
+fn token_instruction_ok(ix: Instruction, encoding: Encoding) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    (
+        StatusCode::OK,
+        AxumJson(TokenInstructionResponse {
+            success: true,
+            data: Some(token_instruction_data(ix, encoding)),
+            error: None,
+        })
+    )
+}
+
+fn parse_pubkey(value: &str, field: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|_| format!("Invalid {} pubkey", field))
+}
+
+// absent and null both mean "not provided"
+fn parse_optional_pubkey(req: &serde_json::Value, field: &str) -> Result<Option<Pubkey>, String> {
+    match req.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_str() {
+            Some(value) => parse_pubkey(value, field).map(Some),
+            None => Err(format!("Invalid {} pubkey", field)),
+        },
+    }
+}
+
+// spl-token stays the default so existing callers keep the legacy program
+fn parse_token_program(req: &serde_json::Value) -> Result<Pubkey, String> {
+    match req.get("tokenProgram") {
+        None | Some(serde_json::Value::Null) => Ok(spl_token::id()),
+        Some(value) => match value.as_str() {
+            Some("spl-token") => Ok(spl_token::id()),
+            Some("token-2022") => Ok(spl_token_2022::id()),
+            Some(other) => Err(format!("Unsupported tokenProgram: {}", other)),
+            None => Err("Invalid tokenProgram".to_string()),
+        },
+    }
+}
+
+// signer pubkeys of an SPL multisig authority, empty for a single-key authority
+fn parse_signers(req: &serde_json::Value) -> Result<Vec<Pubkey>, String> {
+    let values = match req.get("signers") {
+        None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+        Some(serde_json::Value::Array(values)) => values,
+        Some(_) => return Err("signers must be an array".to_string()),
+    };
+    if values.len() > spl_token_2022::instruction::MAX_SIGNERS {
+        return Err(format!("At most {} signers are allowed", spl_token_2022::instruction::MAX_SIGNERS));
+    }
+    let mut signers: Vec<Pubkey> = Vec::with_capacity(values.len());
+    for value in values {
+        let signer = value.as_str()
+            .ok_or_else(|| "Invalid signer pubkey".to_string())
+            .and_then(|v| parse_pubkey(v, "signer"))?;
+        if signers.contains(&signer) {
+            return Err(format!("Duplicate signer {}", signer));
+        }
+        signers.push(signer);
+    }
+    Ok(signers)
+}
+
+/// The optional `memo` as bytes, checked to be UTF-8 as the Memo program requires.
+///
+/// Whether it fits next to the other instructions is checked with `check_memo_fits`.
+fn parse_memo(req: &serde_json::Value) -> Result<Option<Vec<u8>>, String> {
+    let memo = match req.get("memo") {
+        None | Some(serde_json::Value::Null) => return Ok(None),
+        Some(value) => value.as_str().ok_or_else(|| "memo must be a string".to_string())?,
+    };
+    let memo = match parse_text_encoding(req, "memoEncoding")? {
+        None => memo.as_bytes().to_vec(),
+        Some(encoding) => encoding.decode(memo).map_err(|_| format!("Invalid {} memo", encoding.name()))?,
+    };
+    if memo.is_empty() {
+        return Err("Memo must not be empty".to_string());
+    }
+    if std::str::from_utf8(&memo).is_err() {
+        return Err("Memo must be valid UTF-8".to_string());
+    }
+    if memo.len() > PACKET_DATA_SIZE {
+        return Err(format!("Memo exceeds {} bytes", PACKET_DATA_SIZE));
+    }
+    Ok(Some(memo))
+}
+
+// the memo is the last instruction, so anything over the packet size is the memo's to give up
+fn check_memo_fits(instructions: &[Instruction], fee_payer: &Pubkey) -> Result<(), String> {
+    let size = transaction_size(instructions, fee_payer);
+    if size > PACKET_DATA_SIZE {
+        return Err(format!("Memo does not fit in the transaction ({} of {} bytes)", size, PACKET_DATA_SIZE));
+    }
+    Ok(())
+}
+
+/// An optional boolean flag, false when absent; anything else but a boolean is refused.
+fn parse_flag(req: &serde_json::Value, field: &str) -> Result<bool, String> {
+    match req.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| format!("{} must be a boolean", field)),
+    }
+}
+
+fn parse_decimals(req: &serde_json::Value) -> Result<Option<u8>, String> {
+    match req.get("decimals") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(d) if d <= u8::MAX as u64 => Ok(Some(d as u8)),
+            _ => Err("Invalid decimals".to_string()),
+        },
+    }
+}
+
+// returns the payer when `createAta` asks for an idempotent ATA creation
+fn parse_create_ata(req: &serde_json::Value, default_payer: Pubkey) -> Result<Option<Pubkey>, String> {
+    if !parse_flag(req, "createAta")? {
+        return Ok(None);
+    }
+    Ok(Some(parse_optional_pubkey(req, "payer")?.unwrap_or(default_payer)))
+}
+
+// the runtime caps a transaction at this many compute units
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+fn parse_compute_unit_limit(value: &serde_json::Value, field: &str) -> Result<u32, String> {
+    match value.as_u64() {
+        Some(units) if units > 0 && units <= MAX_COMPUTE_UNIT_LIMIT as u64 => Ok(units as u32),
+        _ => Err(format!("{} must be between 1 and {}", field, MAX_COMPUTE_UNIT_LIMIT)),
+    }
+}
+
+fn parse_micro_lamports(value: &serde_json::Value) -> Result<u64, String> {
+    value.as_u64().ok_or_else(|| "microLamports must be a non-negative integer".to_string())
+}
+
+/// Compute budget instructions for an optional `priorityFee` block, to go ahead of the transfer.
+fn parse_priority_fee(req: &serde_json::Value) -> Result<Vec<Instruction>, String> {
+    let fee = match req.get("priorityFee") {
+        None | Some(serde_json::Value::Null) => return Ok(vec![]),
+        Some(fee) if fee.is_object() => fee,
+        Some(_) => return Err("priorityFee must be an object".to_string()),
+    };
+    let mut instructions = Vec::new();
+    if let Some(units) = fee.get("computeUnitLimit").filter(|v| !v.is_null()) {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(parse_compute_unit_limit(units, "computeUnitLimit")?));
+    }
+    if let Some(price) = fee.get("microLamports").filter(|v| !v.is_null()) {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(parse_micro_lamports(price)?));
+    }
+    if instructions.is_empty() {
+        return Err("priorityFee needs computeUnitLimit or microLamports".to_string());
+    }
+    Ok(instructions)
+}
+
+// an explicit `account` wins over the ATA derived from `owner` and `mint`
+fn resolve_token_account(req: &serde_json::Value) -> Result<Pubkey, String> {
+    if let Some(account) = parse_optional_pubkey(req, "account")? {
+        return Ok(account);
+    }
+    let mint = parse_optional_pubkey(req, "mint")?;
+    let owner = parse_optional_pubkey(req, "owner")?;
+    match (owner, mint) {
+        (Some(owner), Some(mint)) => Ok(spl_associated_token_account::get_associated_token_address(&owner, &mint)),
+        _ => Err("Missing required fields".to_string()),
+    }
+}
+
+fn parse_approve_request(req: &serde_json::Value, checked: bool) -> Result<TokenApproveRequest, String> {
+    let mint = req.get("mint").and_then(|v| v.as_str());
+    let owner = req.get("owner").and_then(|v| v.as_str());
+    let delegate = req.get("delegate").and_then(|v| v.as_str());
+    let amount = req.get("amount").and_then(|v| v.as_u64());
+    let decimals = req.get("decimals").and_then(|v| v.as_u64());
+    if mint.is_none() || owner.is_none() || delegate.is_none() || amount.is_none() {
+        return Err("Missing required fields".to_string());
+    }
+    if checked && decimals.is_none() {
+        return Err("Missing required fields".to_string());
+    }
+    let mint = parse_pubkey(mint.unwrap(), "mint")?;
+    let owner = parse_pubkey(owner.unwrap(), "owner")?;
+    let delegate = parse_pubkey(delegate.unwrap(), "delegate")?;
+    if delegate == owner {
+        return Err("Delegate must differ from owner".to_string());
+    }
+    if amount == Some(0) {
+        return Err("Amount must be greater than 0".to_string());
+    }
+    let decimals = match decimals {
+        Some(d) if d > u8::MAX as u64 => return Err("Invalid decimals".to_string()),
+        Some(d) => Some(d as u8),
+        None => None,
+    };
+    Ok(TokenApproveRequest {
+        mint: mint.to_string(),
+        owner: owner.to_string(),
+        delegate: delegate.to_string(),
+        amount: amount.unwrap(),
+        decimals,
+    })
+}
+
+pub async fn token_approve_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let req = match parse_approve_request(&req, false) {
+        Ok(req) => req,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let owner = Pubkey::from_str(&req.owner).unwrap();
+    let source_ata = spl_associated_token_account::get_associated_token_address(
+        &owner,
+        &Pubkey::from_str(&req.mint).unwrap(),
+    );
+    match approve(
+        &spl_token::id(),
+        &source_ata,
+        &Pubkey::from_str(&req.delegate).unwrap(),
+        &owner,
+        &signer_refs,
+        req.amount,
+    ) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    }
+}
+
+pub async fn token_approve_checked_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let req = match parse_approve_request(&req, true) {
+        Ok(req) => req,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let owner = Pubkey::from_str(&req.owner).unwrap();
+    let mint = Pubkey::from_str(&req.mint).unwrap();
+    let source_ata = spl_associated_token_account::get_associated_token_address(&owner, &mint);
+    match approve_checked(
+        &spl_token::id(),
+        &source_ata,
+        &mint,
+        &Pubkey::from_str(&req.delegate).unwrap(),
+        &owner,
+        &signer_refs,
+        req.amount,
+        req.decimals.unwrap(),
+    ) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    }
+}
+
+pub async fn token_revoke_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let mint = req.get("mint").and_then(|v| v.as_str());
+    let owner = req.get("owner").and_then(|v| v.as_str());
+    if mint.is_none() || owner.is_none() {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string());
+    }
+    let mint = match parse_pubkey(mint.unwrap(), "mint") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let owner = match parse_pubkey(owner.unwrap(), "owner") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let req = TokenRevokeRequest {
+        mint: mint.to_string(),
+        owner: owner.to_string(),
+    };
+    let source_ata = spl_associated_token_account::get_associated_token_address(
+        &Pubkey::from_str(&req.owner).unwrap(),
+        &Pubkey::from_str(&req.mint).unwrap(),
+    );
+    match revoke(&spl_token::id(), &source_ata, &owner, &signer_refs) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    }
+}
+
+fn parse_authority_type(value: &str) -> Result<AuthorityType, String> {
+    match value {
+        "mintTokens" => Ok(AuthorityType::MintTokens),
+        "freezeAccount" => Ok(AuthorityType::FreezeAccount),
+        "accountOwner" => Ok(AuthorityType::AccountOwner),
+        "closeAccount" => Ok(AuthorityType::CloseAccount),
+        _ => Err(format!("Unsupported authorityType: {}", value)),
+    }
+}
+
+pub async fn token_set_authority_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let target = req.get("target").and_then(|v| v.as_str());
+    let authority_type = req.get("authorityType").and_then(|v| v.as_str());
+    let current_authority = req.get("currentAuthority").and_then(|v| v.as_str());
+    // newAuthority must be sent explicitly, null revokes the authority
+    if target.is_none() || authority_type.is_none() || current_authority.is_none() || req.get("newAuthority").is_none() {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string());
+    }
+    let parsed_type = match parse_authority_type(authority_type.unwrap()) {
+        Ok(t) => t,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let owned = match (target.unwrap(), &parsed_type) {
+        ("mint", AuthorityType::MintTokens | AuthorityType::FreezeAccount) => {
+            match req.get("mint").and_then(|v| v.as_str()).map(|m| parse_pubkey(m, "mint")) {
+                Some(Ok(mint)) => mint,
+                Some(Err(e)) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+                None => return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+            }
+        }
+        ("account", AuthorityType::AccountOwner | AuthorityType::CloseAccount) => {
+            match resolve_token_account(&req) {
+                Ok(account) => account,
+                Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+            }
+        }
+        ("mint", _) => return token_instruction_error(
+            StatusCode::BAD_REQUEST,
+            format!("authorityType {} does not apply to a mint", authority_type.unwrap()),
+        ),
+        ("account", _) => return token_instruction_error(
+            StatusCode::BAD_REQUEST,
+            format!("authorityType {} does not apply to a token account", authority_type.unwrap()),
+        ),
+        _ => return token_instruction_error(StatusCode::BAD_REQUEST, "target must be \"mint\" or \"account\"".to_string()),
+    };
+    let current = match parse_pubkey(current_authority.unwrap(), "currentAuthority") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let new_authority = match parse_optional_pubkey(&req, "newAuthority") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    if new_authority.is_none() && parsed_type == AuthorityType::AccountOwner {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "Account owner cannot be revoked".to_string());
+    }
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    match set_authority(
+        &spl_token::id(),
+        &owned,
+        new_authority.as_ref(),
+        parsed_type,
+        &current,
+        &signer_refs,
+    ) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    }
+}
+
+pub async fn token_close_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let owner = match req.get("owner").and_then(|v| v.as_str()) {
+        Some(owner) => owner,
+        None => return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+    };
+    let owner = match parse_pubkey(owner, "owner") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match resolve_token_account(&req) {
+        Ok(account) => account,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    // rent goes back to the owner unless another destination is given
+    let destination = match parse_optional_pubkey(&req, "destination") {
+        Ok(destination) => destination.unwrap_or(owner),
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    if destination == account {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "Destination must differ from the closed account".to_string());
+    }
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    match close_account(&spl_token::id(), &account, &destination, &owner, &signer_refs) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    }
+}
+
+pub async fn token_ata_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenAtaResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenAtaResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let owner = req.get("owner").and_then(|v| v.as_str());
+    let mint = req.get("mint").and_then(|v| v.as_str());
+    if owner.is_none() || mint.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenAtaResponse {
+                success: false,
+                data: None,
+                error: Some("Missing required fields".to_string()),
+            })
+        );
+    }
+    let parsed = parse_pubkey(owner.unwrap(), "owner").and_then(|owner| {
+        let mint = parse_pubkey(mint.unwrap(), "mint")?;
+        // the create instruction is only built on request, paid by the owner by default
+        let payer = match parse_flag(&req, "create")? {
+            true => Some(parse_optional_pubkey(&req, "payer")?.unwrap_or(owner)),
+            false => None,
+        };
+        Ok((owner, mint, payer))
+    });
+    let (owner, mint, payer) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return (
+            StatusCode::BAD_REQUEST,
+            AxumJson(TokenAtaResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        ),
+    };
+    let address = spl_associated_token_account::get_associated_token_address(&owner, &mint);
+    let instruction = payer.map(|payer| token_instruction_data(
+        create_associated_token_account_idempotent(&payer, &owner, &mint, &spl_token::id()),
+        encoding,
+    ));
+    let data = TokenAtaResponseData {
+        address: address.to_string(),
+        owner: owner.to_string(),
+        mint: mint.to_string(),
+        instruction,
+    };
+    (
+        StatusCode::OK,
+        AxumJson(TokenAtaResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn wrap_sol_error(error: String) -> (StatusCode, AxumJson<WrapSolResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(WrapSolResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn wrap_sol_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<WrapSolResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return wrap_sol_error(e),
+    };
+    let owner = req.get("owner").and_then(|v| v.as_str());
+    let lamports = match req.get("lamports").and_then(|v| v.as_u64()) {
+        Some(0) => return wrap_sol_error("Amount must be greater than 0".to_string()),
+        Some(val) => val,
+        None => return wrap_sol_error("Missing required fields".to_string()),
+    };
+    let owner = match owner.map(|owner| parse_pubkey(owner, "owner")) {
+        Some(Ok(pk)) => pk,
+        Some(Err(e)) => return wrap_sol_error(e),
+        None => return wrap_sol_error("Missing required fields".to_string()),
+    };
+    let payer = match parse_optional_pubkey(&req, "payer") {
+        Ok(payer) => payer.unwrap_or(owner),
+        Err(e) => return wrap_sol_error(e),
+    };
+    let native_mint = spl_token::native_mint::id();
+    let account = spl_associated_token_account::get_associated_token_address(&owner, &native_mint);
+    // create the wSOL account, fund it, then let the token program pick up the lamports
+    let create_ix = create_associated_token_account_idempotent(&payer, &owner, &native_mint, &spl_token::id());
+    let transfer_ix = system_instruction::transfer(&owner, &account, lamports);
+    let sync_ix = match sync_native(&spl_token::id(), &account) {
+        Ok(ix) => ix,
+        Err(e) => return (
+            StatusCode::OK,
+            AxumJson(WrapSolResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to create instruction: {}", e)),
+            })
+        ),
+    };
+    let data = WrapSolResponseData {
+        account: account.to_string(),
+        instructions: vec![
+            token_instruction_data(create_ix, encoding),
+            token_instruction_data(transfer_ix, encoding),
+            token_instruction_data(sync_ix, encoding),
+        ],
+    };
+    (
+        StatusCode::OK,
+        AxumJson(WrapSolResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+pub async fn unwrap_sol_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<WrapSolResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return wrap_sol_error(e),
+    };
+    let owner = match req.get("owner").and_then(|v| v.as_str()).map(|owner| parse_pubkey(owner, "owner")) {
+        Some(Ok(pk)) => pk,
+        Some(Err(e)) => return wrap_sol_error(e),
+        None => return wrap_sol_error("Missing required fields".to_string()),
+    };
+    // closing the wSOL account returns every lamport, wrapped balance included
+    let destination = match parse_optional_pubkey(&req, "destination") {
+        Ok(destination) => destination.unwrap_or(owner),
+        Err(e) => return wrap_sol_error(e),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return wrap_sol_error(e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let account = spl_associated_token_account::get_associated_token_address(&owner, &spl_token::native_mint::id());
+    let close_ix = match close_account(&spl_token::id(), &account, &destination, &owner, &signer_refs) {
+        Ok(ix) => ix,
+        Err(e) => return (
+            StatusCode::OK,
+            AxumJson(WrapSolResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to create instruction: {}", e)),
+            })
+        ),
+    };
+    let data = WrapSolResponseData {
+        account: account.to_string(),
+        instructions: vec![token_instruction_data(close_ix, encoding)],
+    };
+    (
+        StatusCode::OK,
+        AxumJson(WrapSolResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+pub async fn compute_unit_limit_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let units = match req.get("units").map(|units| parse_compute_unit_limit(units, "units")) {
+        Some(Ok(units)) => units,
+        Some(Err(e)) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+        None => return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+    };
+    token_instruction_ok(ComputeBudgetInstruction::set_compute_unit_limit(units), encoding)
+}
+
+pub async fn compute_unit_price_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let micro_lamports = match req.get("microLamports").map(parse_micro_lamports) {
+        Some(Ok(micro_lamports)) => micro_lamports,
+        Some(Err(e)) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+        None => return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+    };
+    token_instruction_ok(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports), encoding)
+}
+
+pub async fn memo_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let memo = match parse_memo(&req) {
+        Ok(Some(memo)) => memo,
+        Ok(None) => return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    // every signer listed has to sign the transaction carrying the memo
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let ix = spl_memo::build_memo(&memo, &signer_refs);
+    // on its own in a transaction, paid for by the first signer or by one more key
+    let fee_payer = signers.first().copied().unwrap_or_default();
+    if let Err(e) = check_memo_fits(std::slice::from_ref(&ix), &fee_payer) {
+        return token_instruction_error(StatusCode::BAD_REQUEST, e);
+    }
+    token_instruction_ok(ix, encoding)
+}
+
+fn stake_error(error: String) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(StakeInstructionResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+fn stake_ok(
+    stake_account: Pubkey,
+    instructions: Vec<Instruction>,
+    rent_exempt_lamports: Option<u64>,
+    encoding: Encoding,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let data = StakeInstructionResponseData {
+        stake_account: stake_account.to_string(),
+        split_stake_account: None,
+        instructions: instructions.into_iter().map(|ix| token_instruction_data(ix, encoding)).collect(),
+        rent_exempt_lamports,
+    };
+    (
+        StatusCode::OK,
+        AxumJson(StakeInstructionResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn required_pubkey(req: &serde_json::Value, field: &str) -> Result<Pubkey, String> {
+    match req.get(field).and_then(|v| v.as_str()) {
+        Some(value) => parse_pubkey(value, field),
+        None => Err("Missing required fields".to_string()),
+    }
+}
+
+fn required_lamports(req: &serde_json::Value) -> Result<u64, String> {
+    match req.get("lamports").and_then(|v| v.as_u64()) {
+        Some(0) => Err("Amount must be greater than 0".to_string()),
+        Some(lamports) => Ok(lamports),
+        None => Err("Missing required fields".to_string()),
+    }
+}
+
+fn stake_rent_exempt_lamports() -> u64 {
+    Rent::default().minimum_balance(StakeStateV2::size_of())
+}
+
+// every lockup field is optional, an absent lockup never restricts withdrawals
+fn parse_lockup(req: &serde_json::Value) -> Result<Lockup, String> {
+    let lockup = match req.get("lockup") {
+        None | Some(serde_json::Value::Null) => return Ok(Lockup::default()),
+        Some(lockup) if lockup.is_object() => lockup,
+        Some(_) => return Err("lockup must be an object".to_string()),
+    };
+    let unix_timestamp = match lockup.get("unixTimestamp") {
+        None | Some(serde_json::Value::Null) => 0,
+        Some(value) => value.as_i64().ok_or_else(|| "Invalid lockup unixTimestamp".to_string())?,
+    };
+    let epoch = match lockup.get("epoch") {
+        None | Some(serde_json::Value::Null) => 0,
+        Some(value) => value.as_u64().ok_or_else(|| "Invalid lockup epoch".to_string())?,
+    };
+    let custodian = parse_optional_pubkey(lockup, "custodian")?.unwrap_or_default();
+    Ok(Lockup { unix_timestamp, epoch, custodian })
+}
+
+fn parse_stake_authorize(value: &str) -> Result<StakeAuthorize, String> {
+    match value {
+        "staker" => Ok(StakeAuthorize::Staker),
+        "withdrawer" => Ok(StakeAuthorize::Withdrawer),
+        _ => Err(format!("Unsupported authorityType: {}", value)),
+    }
+}
+
+pub async fn stake_create_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let from = match required_pubkey(&req, "from") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let staker = match required_pubkey(&req, "staker") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let withdrawer = match parse_optional_pubkey(&req, "withdrawer") {
+        Ok(withdrawer) => withdrawer.unwrap_or(staker),
+        Err(e) => return stake_error(e),
+    };
+    let lamports = match required_lamports(&req) {
+        Ok(lamports) => lamports,
+        Err(e) => return stake_error(e),
+    };
+    let lockup = match parse_lockup(&req) {
+        Ok(lockup) => lockup,
+        Err(e) => return stake_error(e),
+    };
+    let rent_exempt_lamports = stake_rent_exempt_lamports();
+    if lamports < rent_exempt_lamports {
+        return stake_error(format!("lamports must cover the {} lamport rent-exempt reserve", rent_exempt_lamports));
+    }
+    // system create_account followed by the stake program's initialize
+    let instructions = stake_instruction::create_account(&from, &stake_account, &Authorized { staker, withdrawer }, &lockup, lamports);
+    stake_ok(stake_account, instructions, Some(rent_exempt_lamports), encoding)
+}
+
+pub async fn stake_delegate_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let staker = match required_pubkey(&req, "staker") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let vote_account = match required_pubkey(&req, "voteAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let ix = stake_instruction::delegate_stake(&stake_account, &staker, &vote_account);
+    stake_ok(stake_account, vec![ix], None, encoding)
+}
+
+pub async fn stake_deactivate_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let staker = match required_pubkey(&req, "staker") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let ix = stake_instruction::deactivate_stake(&stake_account, &staker);
+    stake_ok(stake_account, vec![ix], None, encoding)
+}
+
+pub async fn stake_withdraw_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let withdrawer = match required_pubkey(&req, "withdrawer") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let to = match required_pubkey(&req, "to") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let lamports = match required_lamports(&req) {
+        Ok(lamports) => lamports,
+        Err(e) => return stake_error(e),
+    };
+    // the custodian only has to sign while a lockup is in force
+    let custodian = match parse_optional_pubkey(&req, "custodian") {
+        Ok(custodian) => custodian,
+        Err(e) => return stake_error(e),
+    };
+    let ix = stake_instruction::withdraw(&stake_account, &withdrawer, &to, lamports, custodian.as_ref());
+    stake_ok(stake_account, vec![ix], None, encoding)
+}
+
+pub async fn stake_split_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let staker = match required_pubkey(&req, "staker") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let split_stake_account = match required_pubkey(&req, "splitStakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let lamports = match required_lamports(&req) {
+        Ok(lamports) => lamports,
+        Err(e) => return stake_error(e),
+    };
+    if split_stake_account == stake_account {
+        return stake_error("splitStakeAccount must differ from stakeAccount".to_string());
+    }
+    // the split destination has to be rent exempt before the split, a payer can fund it up front
+    let payer = match parse_optional_pubkey(&req, "payer") {
+        Ok(payer) => payer,
+        Err(e) => return stake_error(e),
+    };
+    let rent_exempt_lamports = stake_rent_exempt_lamports();
+    let mut instructions: Vec<Instruction> = payer
+        .map(|payer| system_instruction::transfer(&payer, &split_stake_account, rent_exempt_lamports))
+        .into_iter()
+        .collect();
+    instructions.extend(stake_instruction::split(&stake_account, &staker, lamports, &split_stake_account));
+    let data = StakeInstructionResponseData {
+        stake_account: stake_account.to_string(),
+        split_stake_account: Some(split_stake_account.to_string()),
+        instructions: instructions.into_iter().map(|ix| token_instruction_data(ix, encoding)).collect(),
+        rent_exempt_lamports: Some(rent_exempt_lamports),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(StakeInstructionResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+pub async fn stake_merge_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let source_stake_account = match required_pubkey(&req, "sourceStakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let staker = match required_pubkey(&req, "staker") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    if source_stake_account == stake_account {
+        return stake_error("sourceStakeAccount must differ from stakeAccount".to_string());
+    }
+    // the source is drained into stakeAccount and closed
+    let instructions = stake_instruction::merge(&stake_account, &source_stake_account, &staker);
+    stake_ok(stake_account, instructions, None, encoding)
+}
+
+pub async fn stake_authorize_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<StakeInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return stake_error(e),
+    };
+    let stake_account = match required_pubkey(&req, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let authority = match required_pubkey(&req, "authority") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let new_authority = match required_pubkey(&req, "newAuthority") {
+        Ok(pk) => pk,
+        Err(e) => return stake_error(e),
+    };
+    let authority_type = match req.get("authorityType").and_then(|v| v.as_str()).map(parse_stake_authorize) {
+        Some(Ok(authority_type)) => authority_type,
+        Some(Err(e)) => return stake_error(e),
+        None => return stake_error("Missing required fields".to_string()),
+    };
+    let custodian = match parse_optional_pubkey(&req, "custodian") {
+        Ok(custodian) => custodian,
+        Err(e) => return stake_error(e),
+    };
+    let ix = stake_instruction::authorize(&stake_account, &authority, &new_authority, authority_type, custodian.as_ref());
+    stake_ok(stake_account, vec![ix], None, encoding)
+}
+
+fn extension_error(ix: Result<Instruction, solana_program::program_error::ProgramError>) -> Result<Instruction, String> {
+    ix.map_err(|e| format!("Failed to create instruction: {}", e))
+}
+
+// builds the Token-2022 extension init instructions requested for a new mint,
+// in the order they must run before initialize_mint
+fn build_mint_extensions(
+    extensions: &serde_json::Value,
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    has_freeze_authority: bool,
+) -> Result<(Vec<ExtensionType>, Vec<Instruction>), String> {
+    use spl_token_2022::extension::{default_account_state, interest_bearing_mint, metadata_pointer, transfer_fee};
+    use spl_token_2022::instruction::{initialize_mint_close_authority, initialize_non_transferable_mint, initialize_permanent_delegate};
+
+    if token_program != &spl_token_2022::id() {
+        return Err("Extensions require tokenProgram token-2022".to_string());
+    }
+    if !extensions.is_object() {
+        return Err("Invalid extensions".to_string());
+    }
+    let mut types = Vec::new();
+    let mut ixs = Vec::new();
+    if let Some(fee) = extensions.get("transferFee").filter(|v| !v.is_null()) {
+        let basis_points = fee.get("basisPoints").and_then(|v| v.as_u64());
+        let maximum_fee = fee.get("maximumFee").and_then(|v| v.as_u64());
+        let (basis_points, maximum_fee) = match (basis_points, maximum_fee) {
+            (Some(bps), Some(max)) if bps <= 10_000 => (bps as u16, max),
+            (Some(_), Some(_)) => return Err("transferFee.basisPoints must be at most 10000".to_string()),
+            _ => return Err("Missing required fields".to_string()),
+        };
+        let config_authority = parse_optional_pubkey(fee, "configAuthority")?;
+        let withdraw_authority = parse_optional_pubkey(fee, "withdrawAuthority")?;
+        types.push(ExtensionType::TransferFeeConfig);
+        ixs.push(extension_error(transfer_fee::instruction::initialize_transfer_fee_config(
+            token_program,
+            mint,
+            config_authority.as_ref(),
+            withdraw_authority.as_ref(),
+            basis_points,
+            maximum_fee,
+        ))?);
+    }
+    if let Some(interest) = extensions.get("interestBearing").filter(|v| !v.is_null()) {
+        let rate = match interest.get("rate").and_then(|v| v.as_i64()) {
+            Some(rate) if rate >= i16::MIN as i64 && rate <= i16::MAX as i64 => rate as i16,
+            Some(_) => return Err("interestBearing.rate is out of range".to_string()),
+            None => return Err("Missing required fields".to_string()),
+        };
+        let rate_authority = parse_optional_pubkey(interest, "rateAuthority")?;
+        types.push(ExtensionType::InterestBearingConfig);
+        ixs.push(extension_error(interest_bearing_mint::instruction::initialize(token_program, mint, rate_authority, rate))?);
+    }
+    if parse_flag(extensions, "nonTransferable")? {
+        types.push(ExtensionType::NonTransferable);
+        ixs.push(extension_error(initialize_non_transferable_mint(token_program, mint))?);
+    }
+    if let Some(delegate) = parse_optional_pubkey(extensions, "permanentDelegate")? {
+        types.push(ExtensionType::PermanentDelegate);
+        ixs.push(extension_error(initialize_permanent_delegate(token_program, mint, &delegate))?);
+    }
+    if let Some(close_authority) = parse_optional_pubkey(extensions, "mintCloseAuthority")? {
+        types.push(ExtensionType::MintCloseAuthority);
+        ixs.push(extension_error(initialize_mint_close_authority(token_program, mint, Some(&close_authority)))?);
+    }
+    if let Some(state) = extensions.get("defaultAccountState").filter(|v| !v.is_null()) {
+        let state = match state.as_str() {
+            Some("initialized") => AccountState::Initialized,
+            Some("frozen") if has_freeze_authority => AccountState::Frozen,
+            Some("frozen") => return Err("defaultAccountState frozen requires a freezeAuthority".to_string()),
+            _ => return Err("defaultAccountState must be \"initialized\" or \"frozen\"".to_string()),
+        };
+        types.push(ExtensionType::DefaultAccountState);
+        ixs.push(extension_error(default_account_state::instruction::initialize_default_account_state(token_program, mint, &state))?);
+    }
+    if let Some(pointer) = extensions.get("metadataPointer").filter(|v| !v.is_null()) {
+        let authority = parse_optional_pubkey(pointer, "authority")?;
+        let metadata_address = parse_optional_pubkey(pointer, "metadataAddress")?;
+        if authority.is_none() && metadata_address.is_none() {
+            return Err("metadataPointer needs an authority or metadataAddress".to_string());
+        }
+        types.push(ExtensionType::MetadataPointer);
+        ixs.push(extension_error(metadata_pointer::instruction::initialize(token_program, mint, authority, metadata_address))?);
+    }
+    if types.is_empty() {
+        return Err("No extensions requested".to_string());
+    }
+    Ok((types, ixs))
+}
+
+pub async fn token_multisig_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let multisig = req.get("multisig").and_then(|v| v.as_str());
+    let m = req.get("m").and_then(|v| v.as_u64());
+    if multisig.is_none() || m.is_none() || req.get("signers").is_none() {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string());
+    }
+    let multisig = match parse_pubkey(multisig.unwrap(), "multisig") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signers = match parse_signers(&req) {
+        Ok(signers) => signers,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let m = m.unwrap();
+    if signers.is_empty() || m == 0 || m > signers.len() as u64 {
+        return token_instruction_error(StatusCode::BAD_REQUEST, "m must be between 1 and the number of signers".to_string());
+    }
+    let token_program = match parse_token_program(&req) {
+        Ok(program) => program,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let payer = match parse_optional_pubkey(&req, "payer") {
+        Ok(pk) => pk,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let ix = match spl_token_2022::instruction::initialize_multisig(&token_program, &multisig, &signer_refs, m as u8) {
+        Ok(ix) => ix,
+        Err(e) => return token_instruction_error(StatusCode::OK, format!("Failed to create instruction: {}", e)),
+    };
+    let mut data = token_instruction_data(ix.clone(), encoding);
+    // with a payer the multisig account is created first, sized for the fixed layout
+    if let Some(payer) = payer {
+        let space = spl_token_2022::state::Multisig::LEN;
+        let rent = Rent::default().minimum_balance(space);
+        let create_ix = system_instruction::create_account(&payer, &multisig, rent, space as u64, &token_program);
+        data.instructions = Some(vec![token_instruction_data(create_ix, encoding), token_instruction_data(ix, encoding)]);
+        data.account_size = Some(space);
+        data.rent_exempt_lamports = Some(rent);
+    }
+    (
+        StatusCode::OK,
+        AxumJson(TokenInstructionResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+// account address with the signer/writable flags, when known
+type AccountMetaInput = (String, Option<bool>, Option<bool>);
+
+// account metas as returned by the builders: either bare pubkeys or objects with flags
+fn parse_account_metas(req: &serde_json::Value) -> Result<Vec<AccountMetaInput>, String> {
+    let values = match req.get("accounts") {
+        None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+        Some(serde_json::Value::Array(values)) => values,
+        Some(_) => return Err("accounts must be an array".to_string()),
+    };
+    values.iter().map(|value| {
+        let (pubkey, is_signer, is_writable) = match value {
+            serde_json::Value::String(pubkey) => (pubkey.as_str(), None, None),
+            serde_json::Value::Object(meta) => (
+                meta.get("pubkey").and_then(|v| v.as_str()).ok_or_else(|| "Invalid account pubkey".to_string())?,
+                meta.get("is_signer").or_else(|| meta.get("isSigner")).and_then(|v| v.as_bool()),
+                meta.get("is_writable").or_else(|| meta.get("isWritable")).and_then(|v| v.as_bool()),
+            ),
+            _ => return Err("Invalid account pubkey".to_string()),
+        };
+        Ok((parse_pubkey(pubkey, "account")?.to_string(), is_signer, is_writable))
+    }).collect()
+}
+
+fn decoded_instruction_model(
+    program_id: &Pubkey,
+    accounts: &[AccountMetaInput],
+    data: &[u8],
+) -> Result<DecodedInstructionModel, String> {
+    let decoded = decode_instruction(program_id, data)?;
+    let accounts = accounts.iter().enumerate().map(|(i, (pubkey, is_signer, is_writable))| DecodedAccountModel {
+        name: decoded.account_role(i),
+        pubkey: pubkey.clone(),
+        is_signer: *is_signer,
+        is_writable: *is_writable,
+    }).collect();
+    Ok(DecodedInstructionModel {
+        program: decoded.program.to_string(),
+        program_id: program_id.to_string(),
+        instruction_type: decoded.instruction_type,
+        info: decoded.info,
+        accounts,
+    })
+}
+
+fn decode_instruction_error(error: String) -> (StatusCode, AxumJson<DecodeInstructionResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(DecodeInstructionResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn instruction_decode_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<DecodeInstructionResponse>) {
+    // field names of the builder responses are accepted too, so their output can be pasted back
+    let program_id = req.get("programId").or_else(|| req.get("program_id")).and_then(|v| v.as_str());
+    let data = req.get("data").or_else(|| req.get("instruction_data")).and_then(|v| v.as_str());
+    if program_id.is_none() || data.is_none() {
+        return decode_instruction_error("Missing required fields".to_string());
+    }
+    let program_id = match parse_pubkey(program_id.unwrap(), "programId") {
+        Ok(pk) => pk,
+        Err(e) => return decode_instruction_error(e),
+    };
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return decode_instruction_error(e),
+    };
+    let data = match encoding.decode(data.unwrap()) {
+        Ok(bytes) => bytes,
+        Err(e) => return decode_instruction_error(e),
+    };
+    let accounts = match parse_account_metas(&req) {
+        Ok(accounts) => accounts,
+        Err(e) => return decode_instruction_error(e),
+    };
+    match decoded_instruction_model(&program_id, &accounts, &data) {
+        Ok(decoded) => (
+            StatusCode::OK,
+            AxumJson(DecodeInstructionResponse {
+                success: true,
+                data: Some(decoded),
+                error: None,
+            })
+        ),
+        Err(e) => decode_instruction_error(e),
+    }
+}
+
+fn transaction_inspect_error(error: String) -> (StatusCode, AxumJson<TransactionInspectResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(TransactionInspectResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn transaction_inspect_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TransactionInspectResponse>) {
+    let transaction = match req.get("transaction").and_then(|v| v.as_str()) {
+        Some(transaction) => transaction,
+        None => return transaction_inspect_error("Missing required fields".to_string()),
+    };
+    // wallets hand out base64, so that is the default here
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base64) {
+        Ok(encoding) => encoding,
+        Err(e) => return transaction_inspect_error(e),
+    };
+    // `encoding` describes the submitted transaction, bytes in the response follow `outputEncoding`
+    let output_encoding = match parse_encoding_field(&req, "outputEncoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return transaction_inspect_error(e),
+    };
+    let bytes = match encoding.decode(transaction) {
+        Ok(bytes) => bytes,
+        Err(e) => return transaction_inspect_error(e),
+    };
+    let tx: VersionedTransaction = match bincode::deserialize(&bytes) {
+        Ok(tx) => tx,
+        Err(_) => return transaction_inspect_error("Invalid transaction bytes".to_string()),
+    };
+    if let Err(e) = tx.sanitize() {
+        return transaction_inspect_error(format!("Invalid transaction: {}", e));
+    }
+    let message = &tx.message;
+    let static_keys = message.static_account_keys();
+
+    // v0 messages load extra accounts after the static keys: writable lookups first, then readonly
+    let mut labels: Vec<String> = static_keys.iter().map(|key| key.to_string()).collect();
+    let mut accounts: Vec<TransactionAccountModel> = static_keys.iter().enumerate().map(|(i, key)| TransactionAccountModel {
+        pubkey: Some(key.to_string()),
+        is_signer: message.is_signer(i),
+        is_writable: message.is_maybe_writable(i),
+        lookup_table: None,
+        lookup_index: None,
+    }).collect();
+    let lookups = message.address_table_lookups().unwrap_or(&[]);
+    let loaded = lookups.iter().flat_map(|l| l.writable_indexes.iter().map(move |i| (l.account_key, *i, true)))
+        .chain(lookups.iter().flat_map(|l| l.readonly_indexes.iter().map(move |i| (l.account_key, *i, false))));
+    for (table, index, is_writable) in loaded {
+        labels.push(format!("{}#{}", table, index));
+        accounts.push(TransactionAccountModel {
+            pubkey: None,
+            is_signer: false,
+            is_writable,
+            lookup_table: Some(table.to_string()),
+            lookup_index: Some(index),
+        });
+    }
+
+    let results = tx.verify_with_results();
+    let signatures = tx.signatures.iter().zip(static_keys).zip(results).map(|((signature, signer), valid)| TransactionSignatureModel {
+        signer: signer.to_string(),
+        signature: output_encoding.encode(signature.as_ref()),
+        present: *signature != solana_sdk::signature::Signature::default(),
+        valid,
+    }).collect();
+
+    let instructions = message.instructions().iter().map(|ix| {
+        let program_id = static_keys[ix.program_id_index as usize];
+        let metas: Vec<AccountMetaInput> = ix.accounts.iter()
+            .map(|&i| (labels[i as usize].clone(), Some(accounts[i as usize].is_signer), Some(accounts[i as usize].is_writable)))
+            .collect();
+        let (decoded, error) = match decoded_instruction_model(&program_id, &metas, &ix.data) {
+            Ok(decoded) => (Some(decoded), None),
+            Err(e) => (None, Some(e)),
+        };
+        InspectedInstructionModel {
+            program_id: program_id.to_string(),
+            accounts: metas.into_iter().map(|(label, _, _)| label).collect(),
+            instruction_data: output_encoding.encode(&ix.data),
+            decoded,
+            error,
+        }
+    }).collect();
+
+    let data = TransactionInspectResponseData {
+        version: match message {
+            VersionedMessage::Legacy(_) => "legacy".to_string(),
+            VersionedMessage::V0(_) => "0".to_string(),
+        },
+        fee_payer: static_keys[0].to_string(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        signatures,
+        accounts,
+        instructions,
+    };
+    (
+        StatusCode::OK,
+        AxumJson(TransactionInspectResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn account_decode_error(error: String) -> (StatusCode, AxumJson<AccountDecodeResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(AccountDecodeResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn account_decode_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<AccountDecodeResponse>) {
+    let owner = req.get("owner").and_then(|v| v.as_str());
+    let data = req.get("data").and_then(|v| v.as_str());
+    if owner.is_none() || data.is_none() {
+        return account_decode_error("Missing required fields".to_string());
+    }
+    let owner = match parse_pubkey(owner.unwrap(), "owner") {
+        Ok(pk) => pk,
+        Err(e) => return account_decode_error(e),
+    };
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base64) {
+        Ok(encoding) => encoding,
+        Err(e) => return account_decode_error(e),
+    };
+    let data = match encoding.decode(data.unwrap()) {
+        Ok(bytes) => bytes,
+        Err(e) => return account_decode_error(e),
+    };
+    match decode_account(&owner, &data) {
+        Ok(decoded) => (
+            StatusCode::OK,
+            AxumJson(AccountDecodeResponse {
+                success: true,
+                data: Some(AccountDecodeResponseData {
+                    program: decoded.program.to_string(),
+                    owner: owner.to_string(),
+                    account_type: decoded.account_type.to_string(),
+                    info: decoded.info,
+                }),
+                error: None,
+            })
+        ),
+        Err(e) => account_decode_error(e),
+    }
+}
+
+fn siws_challenge_error(status: StatusCode, error: String) -> (StatusCode, AxumJson<SiwsChallengeResponse>) {
+    (
+        status,
+        AxumJson(SiwsChallengeResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+// optional single-line text field of a SIWS challenge request
+fn parse_siws_field<'a>(req: &'a serde_json::Value, field: &str) -> Result<Option<&'a str>, String> {
+    match req.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_str() {
+            Some(value) if !value.is_empty() && !value.contains('\n') => Ok(Some(value)),
+            _ => Err(format!("Invalid {}", field)),
+        },
+    }
+}
+
+pub async fn siws_challenge_handler(
+    State(store): State<Arc<SiwsStore>>,
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<SiwsChallengeResponse>) {
+    let domain = req.get("domain").and_then(|v| v.as_str());
+    let address = req.get("address").and_then(|v| v.as_str());
+    if domain.is_none() || address.is_none() {
+        return siws_challenge_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string());
+    }
+    let domain = domain.unwrap();
+    if domain.is_empty() || domain.contains(char::is_whitespace) {
+        return siws_challenge_error(StatusCode::BAD_REQUEST, "Invalid domain".to_string());
+    }
+    let address = match parse_pubkey(address.unwrap(), "address") {
+        Ok(pk) => pk,
+        Err(e) => return siws_challenge_error(StatusCode::BAD_REQUEST, e),
+    };
+    let (statement, uri, chain_id) = match (
+        parse_siws_field(&req, "statement"),
+        parse_siws_field(&req, "uri"),
+        parse_siws_field(&req, "chainId"),
+    ) {
+        (Ok(statement), Ok(uri), Ok(chain_id)) => (statement, uri, chain_id.unwrap_or("mainnet")),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return siws_challenge_error(StatusCode::BAD_REQUEST, e),
+    };
+    if uri.is_some_and(|uri| uri.contains(char::is_whitespace)) {
+        return siws_challenge_error(StatusCode::BAD_REQUEST, "Invalid uri".to_string());
+    }
+    if !siws::CHAIN_IDS.contains(&chain_id) {
+        return siws_challenge_error(StatusCode::BAD_REQUEST, format!("Unsupported chainId: {}", chain_id));
+    }
+    let expires_in = match req.get("expiresIn") {
+        None | Some(serde_json::Value::Null) => 600,
+        Some(value) => match value.as_i64() {
+            Some(seconds) if (1..=siws::MAX_CHALLENGE_SECS).contains(&seconds) => seconds,
+            _ => {
+                let error = format!("expiresIn must be between 1 and {} seconds", siws::MAX_CHALLENGE_SECS);
+                return siws_challenge_error(StatusCode::BAD_REQUEST, error);
+            }
+        },
+    };
+    let issued_at = Utc::now();
+    let expiration_time = issued_at + Duration::seconds(expires_in);
+    let message = SiwsMessage {
+        domain: domain.to_string(),
+        address: address.to_string(),
+        statement: statement.map(|s| s.to_string()),
+        uri: uri.map(|s| s.to_string()),
+        version: siws::VERSION.to_string(),
+        chain_id: Some(chain_id.to_string()),
+        nonce: siws::generate_nonce(),
+        issued_at,
+        expiration_time: Some(expiration_time),
+    };
+    if let Err(e) = store.issue(&message, expiration_time) {
+        return siws_challenge_error(StatusCode::TOO_MANY_REQUESTS, e);
+    }
+    let data = SiwsChallengeResponseData {
+        message: message.to_text(),
+        nonce: message.nonce,
+        issued_at: siws::format_time(&issued_at),
+        expiration_time: siws::format_time(&expiration_time),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(SiwsChallengeResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn siws_verify_error(status: StatusCode, error: String) -> (StatusCode, AxumJson<SiwsVerifyResponse>) {
+    (
+        status,
+        AxumJson(SiwsVerifyResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn siws_verify_handler(
+    State(store): State<Arc<SiwsStore>>,
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<SiwsVerifyResponse>) {
+    let message = req.get("message").and_then(|v| v.as_str());
+    let signature = req.get("signature").and_then(|v| v.as_str());
+    if message.is_none() || signature.is_none() {
+        return siws_verify_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string());
+    }
+    let message = message.unwrap();
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return siws_verify_error(StatusCode::BAD_REQUEST, e),
+    };
+    let siws_message = match SiwsMessage::parse(message) {
+        Ok(siws_message) => siws_message,
+        Err(e) => return siws_verify_error(StatusCode::BAD_REQUEST, e),
+    };
+    if siws_message.version != siws::VERSION {
+        return siws_verify_error(StatusCode::BAD_REQUEST, format!("Unsupported SIWS version: {}", siws_message.version));
+    }
+    let address = match parse_pubkey(&siws_message.address, "address") {
+        Ok(pk) => pk,
+        Err(e) => return siws_verify_error(StatusCode::BAD_REQUEST, e),
+    };
+    let signature = match encoding.decode(signature.unwrap()).ok().and_then(|bytes| DalekSignature::from_bytes(&bytes).ok()) {
+        Some(signature) => signature,
+        None => return siws_verify_error(StatusCode::BAD_REQUEST, format!("Invalid {} signature", encoding.name())),
+    };
+    let valid = DalekPublicKey::from_bytes(&address.to_bytes())
+        .map(|pubkey| pubkey.verify(message.as_bytes(), &signature).is_ok())
+        .unwrap_or(false);
+    // the nonce is only spent by a correctly signed message, so a bad attempt can't burn it
+    if !valid {
+        return siws_verify_error(StatusCode::UNAUTHORIZED, "Invalid signature".to_string());
+    }
+    let now = Utc::now();
+    if siws_message.expiration_time.is_some_and(|expiration_time| expiration_time <= now) {
+        return siws_verify_error(StatusCode::UNAUTHORIZED, "Challenge expired".to_string());
+    }
+    if let Err(e) = store.consume(&siws_message.nonce, message, now) {
+        return siws_verify_error(StatusCode::UNAUTHORIZED, e);
+    }
+    let (token, expires_at) = store.create_session(&siws_message.address, now);
+    (
+        StatusCode::OK,
+        AxumJson(SiwsVerifyResponse {
+            success: true,
+            data: Some(SiwsVerifyResponseData {
+                address: siws_message.address,
+                token,
+                expires_at: siws::format_time(&expires_at),
+            }),
+            error: None,
+        })
+    )
+}
+
+fn siws_session_error(status: StatusCode, error: String) -> (StatusCode, AxumJson<SiwsSessionResponse>) {
+    (
+        status,
+        AxumJson(SiwsSessionResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn siws_session_handler(
+    State(store): State<Arc<SiwsStore>>,
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<SiwsSessionResponse>) {
+    let token = match req.get("token").and_then(|v| v.as_str()) {
+        Some(token) => token,
+        None => return siws_session_error(StatusCode::BAD_REQUEST, "Missing required fields".to_string()),
+    };
+    let (address, expires_at) = match store.session(token, Utc::now()) {
+        Some(session) => session,
+        None => return siws_session_error(StatusCode::UNAUTHORIZED, "Invalid or expired session".to_string()),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(SiwsSessionResponse {
+            success: true,
+            data: Some(SiwsSessionResponseData {
+                address,
+                expires_at: siws::format_time(&expires_at),
+            }),
+            error: None,
+        })
+    )
+}
+
+// one signature to check, either given outright or produced from `secret`
+fn parse_ed25519_entry(
+    item: &serde_json::Value,
+    encoding: Encoding,
+    message_encoding: Option<Encoding>,
+) -> Result<Ed25519SignatureEntry, String> {
+    let (message, signature, pubkey) = match item.get("secret").and_then(|v| v.as_str()) {
+        Some(secret) => {
+            let message = item.get("message").and_then(|v| v.as_str()).ok_or_else(|| "Missing required fields".to_string())?;
+            let secret_bytes = bs58::decode(secret).into_vec().map_err(|_| "Invalid base58 secret key".to_string())?;
+            let keypair = Keypair::from_bytes(&secret_bytes).map_err(|_| "Invalid secret key bytes".to_string())?;
+            let message = decode_message(message, message_encoding)?;
+            let signature = keypair.sign_message(&message);
+            (message, signature.into(), keypair.pubkey().to_bytes())
+        }
+        None => {
+            let (message, signature, pubkey) = parse_verify_item(item, encoding, message_encoding)?;
+            // the precompile would fail the whole transaction, so catch it here
+            if pubkey.verify(&message, &signature).is_err() {
+                return Err("Signature verification failed".to_string());
+            }
+            (message, signature.to_bytes(), pubkey.to_bytes())
+        }
+    };
+    Ok(Ed25519SignatureEntry { pubkey, signature, message })
+}
+
+pub async fn ed25519_instruction_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    // `signatures` packs several checks into one instruction, otherwise the request is a single entry
+    let entries = match req.get("signatures") {
+        None | Some(serde_json::Value::Null) => parse_ed25519_entry(&req, encoding, message_encoding).map(|entry| vec![entry]),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| parse_ed25519_entry(item, encoding, message_encoding).map_err(|e| format!("{} at index {}", e, index)))
+            .collect(),
+        Some(_) => Err("signatures must be an array".to_string()),
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    match new_ed25519_instruction(&entries) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::BAD_REQUEST, e),
+    }
+}
+
+// secp256k1 keys and addresses use hex, as on the Ethereum side
+fn parse_secp256k1_secret(value: &str) -> Result<libsecp256k1::SecretKey, String> {
+    Encoding::Hex.decode(value)
+        .ok()
+        .and_then(|bytes| libsecp256k1::SecretKey::parse_slice(&bytes).ok())
+        .ok_or_else(|| "Invalid secp256k1 secret key".to_string())
+}
+
+fn parse_eth_address(value: &str) -> Result<EthAddress, String> {
+    Encoding::Hex.decode(value)
+        .ok()
+        .and_then(|bytes| EthAddress::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| "Invalid ethAddress".to_string())
+}
+
+pub async fn secp256k1_keypair_handler() -> (StatusCode, AxumJson<Secp256k1KeypairResponse>) {
+    let secret = generate_secp256k1_secret();
+    let public_key = libsecp256k1::PublicKey::from_secret_key(&secret);
+    let data = Secp256k1KeypairResponseData {
+        secret: Encoding::Hex.encode(&secret.serialize()),
+        public_key: Encoding::Hex.encode(&public_key.serialize()),
+        eth_address: eth_address_checksummed(&eth_address(&secret)),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(Secp256k1KeypairResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+fn secp256k1_sign_error(error: String) -> (StatusCode, AxumJson<Secp256k1SignResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        AxumJson(Secp256k1SignResponse {
+            success: false,
+            data: None,
+            error: Some(error),
+        })
+    )
+}
+
+pub async fn secp256k1_sign_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<Secp256k1SignResponse>) {
+    let message = req.get("message").and_then(|v| v.as_str());
+    let secret = req.get("secret").and_then(|v| v.as_str());
+    if message.is_none() || secret.is_none() {
+        return secp256k1_sign_error("Missing required fields".to_string());
+    }
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return secp256k1_sign_error(e),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return secp256k1_sign_error(e),
+    };
+    let secret = match parse_secp256k1_secret(secret.unwrap()) {
+        Ok(secret) => secret,
+        Err(e) => return secp256k1_sign_error(e),
+    };
+    let message = match decode_message(message.unwrap(), message_encoding) {
+        Ok(bytes) => bytes,
+        Err(e) => return secp256k1_sign_error(e),
+    };
+    let (signature, recovery_id) = secp256k1_sign(&secret, &message);
+    let data = Secp256k1SignResponseData {
+        signature: encoding.encode(&signature),
+        recovery_id,
+        message_hash: Encoding::Hex.encode(&solana_program::keccak::hash(&message).to_bytes()),
+        eth_address: eth_address_checksummed(&eth_address(&secret)),
+    };
+    (
+        StatusCode::OK,
+        AxumJson(Secp256k1SignResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    )
+}
+
+// one signature to check, either given with its recovery id and address or produced from `secret`
+fn parse_secp256k1_entry(
+    item: &serde_json::Value,
+    encoding: Encoding,
+    message_encoding: Option<Encoding>,
+) -> Result<Secp256k1SignatureEntry, String> {
+    let message = item.get("message").and_then(|v| v.as_str()).ok_or_else(|| "Missing required fields".to_string())?;
+    let message = decode_message(message, message_encoding)?;
+    if let Some(secret) = item.get("secret").and_then(|v| v.as_str()) {
+        let secret = parse_secp256k1_secret(secret)?;
+        let (signature, recovery_id) = secp256k1_sign(&secret, &message);
+        return Ok(Secp256k1SignatureEntry { eth_address: eth_address(&secret), signature, recovery_id, message });
+    }
+    let signature = item.get("signature").and_then(|v| v.as_str());
+    let recovery_id = item.get("recoveryId").and_then(|v| v.as_u64());
+    let address = item.get("ethAddress").and_then(|v| v.as_str());
+    if signature.is_none() || recovery_id.is_none() || address.is_none() {
+        return Err("Missing required fields".to_string());
+    }
+    let signature = encoding.decode(signature.unwrap()).map_err(|_| format!("Invalid {} signature", encoding.name()))?;
+    let signature: [u8; 64] = signature.try_into().map_err(|_| "Signature must be 64 bytes".to_string())?;
+    let recovery_id = u8::try_from(recovery_id.unwrap()).map_err(|_| "Invalid recoveryId".to_string())?;
+    let address = parse_eth_address(address.unwrap())?;
+    // the precompile would fail the whole transaction, so catch it here
+    if secp256k1_recover(&message, &signature, recovery_id)? != address {
+        return Err("Signature verification failed".to_string());
+    }
+    Ok(Secp256k1SignatureEntry { eth_address: address, signature, recovery_id, message })
+}
+
+pub async fn secp256k1_instruction_handler(
+    AxumJson(req): AxumJson<serde_json::Value>,
+) -> (StatusCode, AxumJson<TokenInstructionResponse>) {
+    let encoding = match parse_encoding_field(&req, "encoding", Encoding::Base58) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    let message_encoding = match parse_message_encoding(&req) {
+        Ok(encoding) => encoding,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    // the offsets name the instruction holding the data, i.e. this one's index in the transaction
+    let instruction_index = match req.get("instructionIndex") {
+        None | Some(serde_json::Value::Null) => 0,
+        Some(value) => match value.as_u64().and_then(|index| u8::try_from(index).ok()) {
+            Some(index) => index,
+            None => return token_instruction_error(StatusCode::BAD_REQUEST, "Invalid instructionIndex".to_string()),
+        },
+    };
+    let entries = match req.get("signatures") {
+        None | Some(serde_json::Value::Null) => parse_secp256k1_entry(&req, encoding, message_encoding).map(|entry| vec![entry]),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| parse_secp256k1_entry(item, encoding, message_encoding).map_err(|e| format!("{} at index {}", e, index)))
+            .collect(),
+        Some(_) => Err("signatures must be an array".to_string()),
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => return token_instruction_error(StatusCode::BAD_REQUEST, e),
+    };
+    match new_secp256k1_instruction(&entries, instruction_index) {
+        Ok(ix) => token_instruction_ok(ix, encoding),
+        Err(e) => token_instruction_error(StatusCode::BAD_REQUEST, e),
+    }
+}
@@ -1,6 +1,12 @@
+mod decoder;
+mod ed25519_strict;
+mod encoding;
 mod handlers;
 mod models;
+mod offchain;
+mod precompiles;
 mod routes;
+mod siws;
 
 
 use std::net::SocketAddr;
@@ -14,8 +14,9 @@ pub struct KeypairResponse {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TokenCreateRequest {
-    pub mintAuthority: String,
+    pub mint_authority: String,
     pub mint: String,
     pub decimals: u8,
 }
@@ -32,6 +33,14 @@ pub struct TokenInstructionResponseData {
     pub program_id: String,
     pub accounts: Vec<AccountMetaModel>,
     pub instruction_data: String,
+    /// Full ordered instruction list when helper instructions are added around this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<TokenInstructionResponseData>>,
+    /// Mint account size and rent, reported when the account layout depends on the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_lamports: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -47,12 +56,14 @@ pub struct TokenMintRequest {
     pub destination: String,
     pub authority: String,
     pub amount: u64,
+    #[serde(rename = "createAta")]
+    pub create_ata: bool,
+    pub payer: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct MessageSignRequest {
     pub message: String,
-    pub secret: String,
 }
 
 #[derive(Serialize)]
@@ -61,6 +72,23 @@ pub struct MessageSignResponseData {
     #[serde(rename = "pubkey")]
     pub pubkey: String,
     pub message: String,
+    pub message_length: usize,
+    pub message_sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offchain: Option<OffchainMessageModel>,
+}
+
+/// Off-chain message envelope that was actually signed or verified.
+#[derive(Serialize)]
+pub struct OffchainMessageModel {
+    pub version: u8,
+    pub header: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_domain: Option<String>,
+    pub format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub serialized_message: String,
 }
 
 #[derive(Serialize)]
@@ -73,15 +101,21 @@ pub struct MessageSignResponse {
 #[derive(Deserialize)]
 pub struct MessageVerifyRequest {
     pub message: String,
-    pub signature: String,
     pub pubkey: String,
 }
 
 #[derive(Serialize)]
 pub struct MessageVerifyResponseData {
     pub valid: bool,
+    pub strict: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
     pub message: String,
     pub pubkey: String,
+    pub message_length: usize,
+    pub message_sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offchain: Option<OffchainMessageModel>,
 }
 
 #[derive(Serialize)]
@@ -96,6 +130,7 @@ pub struct SendSolRequest {
     pub from: String,
     pub to: String,
     pub lamports: u64,
+    pub memo: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -103,6 +138,8 @@ pub struct SendSolResponseData {
     pub program_id: String,
     pub accounts: Vec<String>,
     pub instruction_data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<TokenInstructionResponseData>>,
 }
 
 #[derive(Serialize)]
@@ -118,12 +155,19 @@ pub struct SendTokenRequest {
     pub mint: String,
     pub owner: String,
     pub amount: u64,
+    pub delegate: Option<String>,
+    pub decimals: Option<u8>,
+    #[serde(rename = "createAta")]
+    pub create_ata: bool,
+    pub payer: Option<String>,
+    pub memo: Option<String>,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SendTokenAccountMeta {
     pub pubkey: String,
-    pub isSigner: bool,
+    pub is_signer: bool,
 }
 
 #[derive(Serialize)]
@@ -131,6 +175,9 @@ pub struct SendTokenResponseData {
     pub program_id: String,
     pub accounts: Vec<SendTokenAccountMeta>,
     pub instruction_data: String,
+    pub instruction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<TokenInstructionResponseData>>,
 }
 
 #[derive(Serialize)]
@@ -138,4 +185,283 @@ pub struct SendTokenResponse {
     pub success: bool,
     pub data: Option<SendTokenResponseData>,
     pub error: Option<String>,
-} 
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct SendBatchTransactionModel {
+    /// Indices into the request's recipients paid by this transaction
+    pub recipients: Vec<usize>,
+    pub instructions: Vec<TokenInstructionResponseData>,
+    pub size: usize,
+    pub compute_units: u64,
+}
+
+#[derive(Serialize)]
+pub struct SendBatchResponseData {
+    pub fee_payer: String,
+    pub transfer_count: usize,
+    pub transactions: Vec<SendBatchTransactionModel>,
+}
+
+#[derive(Serialize)]
+pub struct SendBatchResponse {
+    pub success: bool,
+    pub data: Option<SendBatchResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenApproveRequest {
+    pub mint: String,
+    pub owner: String,
+    pub delegate: String,
+    pub amount: u64,
+    pub decimals: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenRevokeRequest {
+    pub mint: String,
+    pub owner: String,
+}
+
+#[derive(Serialize)]
+pub struct TokenAtaResponseData {
+    pub address: String,
+    pub owner: String,
+    pub mint: String,
+    pub instruction: Option<TokenInstructionResponseData>,
+}
+
+#[derive(Serialize)]
+pub struct TokenAtaResponse {
+    pub success: bool,
+    pub data: Option<TokenAtaResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct WrapSolResponseData {
+    pub account: String,
+    pub instructions: Vec<TokenInstructionResponseData>,
+}
+
+#[derive(Serialize)]
+pub struct WrapSolResponse {
+    pub success: bool,
+    pub data: Option<WrapSolResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct StakeInstructionResponseData {
+    pub stake_account: String,
+    /// New account receiving the lamports, reported by /stake/split
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_stake_account: Option<String>,
+    pub instructions: Vec<TokenInstructionResponseData>,
+    /// Reserve a new stake account needs, reported when the request creates one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_lamports: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct StakeInstructionResponse {
+    pub success: bool,
+    pub data: Option<StakeInstructionResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedAccountModel {
+    pub name: String,
+    pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_signer: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_writable: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedInstructionModel {
+    pub program: String,
+    pub program_id: String,
+    #[serde(rename = "type")]
+    pub instruction_type: String,
+    pub info: serde_json::Value,
+    pub accounts: Vec<DecodedAccountModel>,
+}
+
+#[derive(Serialize)]
+pub struct DecodeInstructionResponse {
+    pub success: bool,
+    pub data: Option<DecodedInstructionModel>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionSignatureModel {
+    pub signer: String,
+    pub signature: String,
+    pub present: bool,
+    pub valid: bool,
+}
+
+#[derive(Serialize)]
+pub struct TransactionAccountModel {
+    /// None for accounts loaded from an address lookup table, which can't be resolved offline
+    pub pubkey: Option<String>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup_table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup_index: Option<u8>,
+}
+
+#[derive(Serialize)]
+pub struct InspectedInstructionModel {
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    pub instruction_data: String,
+    pub decoded: Option<DecodedInstructionModel>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionInspectResponseData {
+    pub version: String,
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    pub signatures: Vec<TransactionSignatureModel>,
+    pub accounts: Vec<TransactionAccountModel>,
+    pub instructions: Vec<InspectedInstructionModel>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionInspectResponse {
+    pub success: bool,
+    pub data: Option<TransactionInspectResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AccountDecodeResponseData {
+    pub program: String,
+    pub owner: String,
+    #[serde(rename = "type")]
+    pub account_type: String,
+    pub info: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct AccountDecodeResponse {
+    pub success: bool,
+    pub data: Option<AccountDecodeResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SiwsChallengeResponseData {
+    pub message: String,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: String,
+}
+
+#[derive(Serialize)]
+pub struct SiwsChallengeResponse {
+    pub success: bool,
+    pub data: Option<SiwsChallengeResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SiwsVerifyResponseData {
+    pub address: String,
+    pub token: String,
+    pub expires_at: String,
+}
+
+#[derive(Serialize)]
+pub struct SiwsVerifyResponse {
+    pub success: bool,
+    pub data: Option<SiwsVerifyResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SiwsSessionResponseData {
+    pub address: String,
+    pub expires_at: String,
+}
+
+#[derive(Serialize)]
+pub struct SiwsSessionResponse {
+    pub success: bool,
+    pub data: Option<SiwsSessionResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MessageVerifyBatchItemModel {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MessageVerifyBatchResponseData {
+    pub all_valid: bool,
+    pub valid_count: usize,
+    pub results: Vec<MessageVerifyBatchItemModel>,
+}
+
+#[derive(Serialize)]
+pub struct MessageVerifyBatchResponse {
+    pub success: bool,
+    pub data: Option<MessageVerifyBatchResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MessageSignBatchResponseData {
+    pub pubkey: String,
+    pub signatures: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct MessageSignBatchResponse {
+    pub success: bool,
+    pub data: Option<MessageSignBatchResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Secp256k1KeypairResponseData {
+    pub secret: String,
+    pub public_key: String,
+    pub eth_address: String,
+}
+
+#[derive(Serialize)]
+pub struct Secp256k1KeypairResponse {
+    pub success: bool,
+    pub data: Option<Secp256k1KeypairResponseData>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Secp256k1SignResponseData {
+    pub signature: String,
+    pub recovery_id: u8,
+    pub message_hash: String,
+    pub eth_address: String,
+}
+
+#[derive(Serialize)]
+pub struct Secp256k1SignResponse {
+    pub success: bool,
+    pub data: Option<Secp256k1SignResponseData>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,131 @@
+use solana_program::pubkey::Pubkey;
+use solana_sdk::offchain_message::{is_printable_ascii, is_utf8, v0, MessageFormat, OffchainMessage as SdkOffchainMessage};
+
+// the SDK only serializes the earlier header without application domain and signers,
+// so the layout is written here and the SDK supplies the shared pieces
+pub const HEADER_VERSION: u8 = 0;
+/// Whole serialized message limit for the ASCII and limited UTF-8 formats, so hardware wallets can hold it.
+pub const MAX_LEN_LEDGER: usize = 1232;
+/// Message body limit of the extended UTF-8 format.
+pub const MAX_LEN: usize = u16::MAX as usize;
+
+pub fn format_name(format: MessageFormat) -> &'static str {
+    match format {
+        MessageFormat::RestrictedAscii => "ascii",
+        MessageFormat::LimitedUtf8 => "utf8",
+        MessageFormat::ExtendedUtf8 => "extended",
+    }
+}
+
+/// Which version 0 header is written in front of the message body.
+#[derive(Default)]
+pub enum Header {
+    /// Format and length only, as serialized by the SDK and `solana sign-offchain-message`.
+    #[default]
+    Legacy,
+    /// Application domain and signers as well, as in the off-chain message proposal.
+    Extended { application_domain: Pubkey, signers: Vec<Pubkey> },
+}
+
+impl Header {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Header::Legacy => "legacy",
+            Header::Extended { .. } => "extended",
+        }
+    }
+}
+
+/// Version 0 off-chain message: header and the message body.
+pub struct OffchainMessage {
+    pub header: Header,
+    pub format: MessageFormat,
+    pub message: Vec<u8>,
+}
+
+impl OffchainMessage {
+    /// Picks the most restrictive format the message fits in.
+    pub fn new(header: Header, message: Vec<u8>) -> Result<Self, String> {
+        if message.is_empty() {
+            return Err("Off-chain message must not be empty".to_string());
+        }
+        let (application_domain, signers) = match header {
+            Header::Legacy => {
+                if !is_utf8(&message) {
+                    return Err("Off-chain message must be valid UTF-8".to_string());
+                }
+                let format = SdkOffchainMessage::new(HEADER_VERSION, &message)
+                    .map_err(|_| format!("Off-chain message exceeds {} bytes", v0::OffchainMessage::MAX_LEN))?
+                    .get_format();
+                return Ok(OffchainMessage { header, format, message });
+            }
+            Header::Extended { application_domain, signers } => (application_domain, signers),
+        };
+        if signers.is_empty() || signers.len() > u8::MAX as usize {
+            return Err(format!("Off-chain messages need between 1 and {} signers", u8::MAX));
+        }
+        let fits_ledger = Self::header_len(signers.len()) + message.len() <= MAX_LEN_LEDGER;
+        let format = if fits_ledger && is_printable_ascii(&message) {
+            MessageFormat::RestrictedAscii
+        } else if !is_utf8(&message) {
+            return Err("Off-chain message must be valid UTF-8".to_string());
+        } else if fits_ledger {
+            MessageFormat::LimitedUtf8
+        } else if message.len() <= MAX_LEN {
+            MessageFormat::ExtendedUtf8
+        } else {
+            return Err(format!("Off-chain message exceeds {} bytes", MAX_LEN));
+        };
+        let header = Header::Extended { application_domain, signers };
+        Ok(OffchainMessage { header, format, message })
+    }
+
+    fn header_len(signer_count: usize) -> usize {
+        // signing domain, version, application domain, format, signer count, signers, message length
+        SdkOffchainMessage::SIGNING_DOMAIN.len() + 1 + 32 + 1 + 1 + signer_count * 32 + 2
+    }
+
+    /// Bytes that get signed: signing domain, header and message body.
+    pub fn serialize(&self) -> Vec<u8> {
+        let signer_count = match &self.header {
+            Header::Legacy => 0,
+            Header::Extended { signers, .. } => signers.len(),
+        };
+        let mut data = Vec::with_capacity(Self::header_len(signer_count) + self.message.len());
+        data.extend_from_slice(SdkOffchainMessage::SIGNING_DOMAIN);
+        data.push(HEADER_VERSION);
+        match &self.header {
+            Header::Legacy => data.push(self.format.into()),
+            Header::Extended { application_domain, signers } => {
+                data.extend_from_slice(application_domain.as_ref());
+                data.push(self.format.into());
+                data.push(signers.len() as u8);
+                for signer in signers {
+                    data.extend_from_slice(signer.as_ref());
+                }
+            }
+        }
+        data.extend_from_slice(&(self.message.len() as u16).to_le_bytes());
+        data.extend_from_slice(&self.message);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn default_header_matches_sdk() {
+        let keypair = Keypair::new();
+        for message in ["hello", "héllo", &"a".repeat(2000)] {
+            let ours = OffchainMessage::new(Header::default(), message.as_bytes().to_vec()).unwrap();
+            let sdk = SdkOffchainMessage::new(HEADER_VERSION, message.as_bytes()).unwrap();
+            assert_eq!(ours.serialize(), sdk.serialize().unwrap());
+            let signature = keypair.sign_message(&ours.serialize());
+            assert!(sdk.verify(&keypair.pubkey(), &signature).unwrap());
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use solana_program::instruction::Instruction;
+use solana_program::keccak;
+use solana_sdk::ed25519_instruction::{
+    PUBKEY_SERIALIZED_SIZE, SIGNATURE_OFFSETS_SERIALIZED_SIZE, SIGNATURE_OFFSETS_START, SIGNATURE_SERIALIZED_SIZE,
+};
+use solana_sdk::secp256k1_instruction::{
+    construct_eth_pubkey, SecpSignatureOffsets, HASHED_PUBKEY_SERIALIZED_SIZE,
+    SIGNATURE_OFFSETS_SERIALIZED_SIZE as SECP_SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+    SIGNATURE_SERIALIZED_SIZE as SECP_SIGNATURE_SERIALIZED_SIZE,
+};
+
+/// Offsets pointing at this same instruction rather than another one in the transaction.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub struct Ed25519SignatureEntry {
+    pub pubkey: [u8; PUBKEY_SERIALIZED_SIZE],
+    pub signature: [u8; SIGNATURE_SERIALIZED_SIZE],
+    pub message: Vec<u8>,
+}
+
+/// Builds one Ed25519 program instruction checking every entry.
+///
+/// The SDK only packs a single signature, so the layout is written here: the signature
+/// count and padding byte, one offsets record per entry, then each entry's pubkey,
+/// signature and message.
+pub fn new_ed25519_instruction(entries: &[Ed25519SignatureEntry]) -> Result<Instruction, String> {
+    if entries.is_empty() || entries.len() > u8::MAX as usize {
+        return Err(format!("Between 1 and {} signatures are allowed", u8::MAX));
+    }
+    let mut data = vec![entries.len() as u8, 0];
+    let mut payload = Vec::new();
+    let payload_start = SIGNATURE_OFFSETS_START + entries.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    for entry in entries {
+        let public_key_offset = payload_start + payload.len();
+        let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+        // offsets are u16, so the whole instruction has to stay addressable
+        if message_data_offset + entry.message.len() > u16::MAX as usize {
+            return Err("Signatures and messages exceed the Ed25519 instruction size".to_string());
+        }
+        for value in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            public_key_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_data_offset as u16,
+            entry.message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        payload.extend_from_slice(&entry.pubkey);
+        payload.extend_from_slice(&entry.signature);
+        payload.extend_from_slice(&entry.message);
+    }
+    data.extend_from_slice(&payload);
+    Ok(Instruction {
+        program_id: solana_sdk::ed25519_program::id(),
+        accounts: vec![],
+        data,
+    })
+}
+
+pub type EthAddress = [u8; HASHED_PUBKEY_SERIALIZED_SIZE];
+
+pub struct Secp256k1SignatureEntry {
+    pub eth_address: EthAddress,
+    pub signature: [u8; SECP_SIGNATURE_SERIALIZED_SIZE],
+    pub recovery_id: u8,
+    pub message: Vec<u8>,
+}
+
+pub fn generate_secp256k1_secret() -> libsecp256k1::SecretKey {
+    // libsecp256k1 wants a rand 0.7 generator, so draw bytes until they form a valid scalar
+    loop {
+        let bytes: [u8; 32] = rand::random();
+        if let Ok(secret) = libsecp256k1::SecretKey::parse(&bytes) {
+            return secret;
+        }
+    }
+}
+
+pub fn eth_address(secret: &libsecp256k1::SecretKey) -> EthAddress {
+    construct_eth_pubkey(&libsecp256k1::PublicKey::from_secret_key(secret))
+}
+
+/// EIP-55 mixed-case checksum form of an Ethereum address.
+pub fn eth_address_checksummed(address: &EthAddress) -> String {
+    let lower: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = keccak::hash(lower.as_bytes()).to_bytes();
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// Signs keccak256(message), the digest the secp256k1 program recovers against.
+pub fn secp256k1_sign(secret: &libsecp256k1::SecretKey, message: &[u8]) -> ([u8; SECP_SIGNATURE_SERIALIZED_SIZE], u8) {
+    let digest = libsecp256k1::Message::parse(&keccak::hash(message).to_bytes());
+    let (signature, recovery_id) = libsecp256k1::sign(&digest, secret);
+    (signature.serialize(), recovery_id.serialize())
+}
+
+/// Recovers the signer of keccak256(message), as the secp256k1 program does.
+pub fn secp256k1_recover(message: &[u8], signature: &[u8; SECP_SIGNATURE_SERIALIZED_SIZE], recovery_id: u8) -> Result<EthAddress, String> {
+    let digest = libsecp256k1::Message::parse(&keccak::hash(message).to_bytes());
+    let signature = libsecp256k1::Signature::parse_standard(signature).map_err(|_| "Invalid secp256k1 signature".to_string())?;
+    let recovery_id = libsecp256k1::RecoveryId::parse(recovery_id).map_err(|_| "Invalid recoveryId".to_string())?;
+    let pubkey = libsecp256k1::recover(&digest, &signature, &recovery_id).map_err(|_| "Signature recovery failed".to_string())?;
+    Ok(construct_eth_pubkey(&pubkey))
+}
+
+/// Builds one secp256k1 program instruction checking every entry.
+///
+/// Unlike the SDK helper this packs several signatures, and `instruction_index` is the
+/// position the instruction will take in its transaction, which the offsets refer to.
+pub fn new_secp256k1_instruction(entries: &[Secp256k1SignatureEntry], instruction_index: u8) -> Result<Instruction, String> {
+    if entries.is_empty() || entries.len() > u8::MAX as usize {
+        return Err(format!("Between 1 and {} signatures are allowed", u8::MAX));
+    }
+    let mut data = vec![entries.len() as u8];
+    let mut payload = Vec::new();
+    let payload_start = 1 + entries.len() * SECP_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    for entry in entries {
+        let eth_address_offset = payload_start + payload.len();
+        let signature_offset = eth_address_offset + HASHED_PUBKEY_SERIALIZED_SIZE;
+        // the recovery id byte follows the signature
+        let message_data_offset = signature_offset + SECP_SIGNATURE_SERIALIZED_SIZE + 1;
+        if message_data_offset + entry.message.len() > u16::MAX as usize {
+            return Err("Signatures and messages exceed the secp256k1 instruction size".to_string());
+        }
+        let offsets = SecpSignatureOffsets {
+            signature_offset: signature_offset as u16,
+            signature_instruction_index: instruction_index,
+            eth_address_offset: eth_address_offset as u16,
+            eth_address_instruction_index: instruction_index,
+            message_data_offset: message_data_offset as u16,
+            message_data_size: entry.message.len() as u16,
+            message_instruction_index: instruction_index,
+        };
+        data.extend_from_slice(&bincode::serialize(&offsets).map_err(|e| e.to_string())?);
+        payload.extend_from_slice(&entry.eth_address);
+        payload.extend_from_slice(&entry.signature);
+        payload.push(entry.recovery_id);
+        payload.extend_from_slice(&entry.message);
+    }
+    data.extend_from_slice(&payload);
+    Ok(Instruction {
+        program_id: solana_sdk::secp256k1_program::id(),
+        accounts: vec![],
+        data,
+    })
+}
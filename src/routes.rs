@@ -1,13 +1,48 @@
 use axum::{Router, routing::post};
 use crate::handlers::*;
+use crate::siws::SiwsStore;
+use std::sync::Arc;
 
 pub fn create_router() -> Router {
     Router::new()
         .route("/keypair", post(keypair_handler))
+        .route("/secp256k1/keypair", post(secp256k1_keypair_handler))
+        .route("/secp256k1/sign", post(secp256k1_sign_handler))
         .route("/token/create", post(token_create_handler))
         .route("/token/mint", post(token_mint_handler))
+        .route("/token/approve", post(token_approve_handler))
+        .route("/token/approve-checked", post(token_approve_checked_handler))
+        .route("/token/revoke", post(token_revoke_handler))
+        .route("/token/set-authority", post(token_set_authority_handler))
+        .route("/token/close", post(token_close_handler))
+        .route("/token/ata", post(token_ata_handler))
+        .route("/token/multisig", post(token_multisig_handler))
+        .route("/token/wrap-sol", post(wrap_sol_handler))
+        .route("/token/unwrap-sol", post(unwrap_sol_handler))
+        .route("/compute-budget/unit-limit", post(compute_unit_limit_handler))
+        .route("/compute-budget/unit-price", post(compute_unit_price_handler))
+        .route("/memo", post(memo_handler))
+        .route("/stake/create", post(stake_create_handler))
+        .route("/stake/delegate", post(stake_delegate_handler))
+        .route("/stake/deactivate", post(stake_deactivate_handler))
+        .route("/stake/withdraw", post(stake_withdraw_handler))
+        .route("/stake/split", post(stake_split_handler))
+        .route("/stake/merge", post(stake_merge_handler))
+        .route("/stake/authorize", post(stake_authorize_handler))
         .route("/message/sign", post(message_sign_handler))
+        .route("/message/sign-batch", post(message_sign_batch_handler))
         .route("/message/verify", post(message_verify_handler))
+        .route("/message/verify-batch", post(message_verify_batch_handler))
         .route("/send/sol", post(send_sol_handler))
         .route("/send/token", post(send_token_handler))
+        .route("/send/batch", post(send_batch_handler))
+        .route("/instruction/decode", post(instruction_decode_handler))
+        .route("/instruction/ed25519", post(ed25519_instruction_handler))
+        .route("/instruction/secp256k1", post(secp256k1_instruction_handler))
+        .route("/transaction/inspect", post(transaction_inspect_handler))
+        .route("/account/decode", post(account_decode_handler))
+        .route("/auth/siws/challenge", post(siws_challenge_handler))
+        .route("/auth/siws/verify", post(siws_verify_handler))
+        .route("/auth/siws/session", post(siws_session_handler))
+        .with_state(Arc::new(SiwsStore::default()))
 } 
\ No newline at end of file
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rand::distributions::Alphanumeric;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub const VERSION: &str = "1";
+pub const CHAIN_IDS: [&str; 4] = ["mainnet", "devnet", "testnet", "localnet"];
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+const NONCE_LEN: usize = 16;
+const SESSION_TTL_HOURS: i64 = 24;
+pub const MAX_CHALLENGE_SECS: i64 = 900;
+// unexpired challenges held at once; past this, new ones are refused until old ones lapse
+const MAX_CHALLENGES: usize = 10_000;
+
+/// Fields of a Sign-In With Solana message, in the order they are written.
+pub struct SiwsMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub version: String,
+    pub chain_id: Option<String>,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+pub fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_time(value: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| format!("Invalid {} in SIWS message", field))
+}
+
+impl SiwsMessage {
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}{}\n{}", self.domain, HEADER_SUFFIX, self.address);
+        if let Some(statement) = &self.statement {
+            text.push_str(&format!("\n\n{}", statement));
+        }
+        let mut fields = Vec::new();
+        if let Some(uri) = &self.uri {
+            fields.push(format!("URI: {}", uri));
+        }
+        fields.push(format!("Version: {}", self.version));
+        if let Some(chain_id) = &self.chain_id {
+            fields.push(format!("Chain ID: {}", chain_id));
+        }
+        fields.push(format!("Nonce: {}", self.nonce));
+        fields.push(format!("Issued At: {}", format_time(&self.issued_at)));
+        if let Some(expiration_time) = &self.expiration_time {
+            fields.push(format!("Expiration Time: {}", format_time(expiration_time)));
+        }
+        text.push_str(&format!("\n\n{}", fields.join("\n")));
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<SiwsMessage, String> {
+        let invalid = || "Invalid SIWS message".to_string();
+        let mut lines = text.splitn(3, '\n');
+        let domain = lines.next().and_then(|line| line.strip_suffix(HEADER_SUFFIX)).ok_or_else(invalid)?;
+        let address = lines.next().ok_or_else(invalid)?;
+        let rest = lines.next().and_then(|rest| rest.strip_prefix('\n')).ok_or_else(invalid)?;
+        // the statement is optional, so a single block after the address holds the fields
+        let (statement, fields) = match rest.split_once("\n\n") {
+            Some((statement, fields)) => (Some(statement), fields),
+            None => (None, rest),
+        };
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        for line in fields.split('\n') {
+            let (key, value) = line.split_once(": ").ok_or_else(invalid)?;
+            if values.insert(key, value).is_some() {
+                return Err(format!("Duplicate {} in SIWS message", key));
+            }
+        }
+        for key in values.keys() {
+            if !["URI", "Version", "Chain ID", "Nonce", "Issued At", "Expiration Time"].contains(key) {
+                return Err(format!("Unsupported SIWS field: {}", key));
+            }
+        }
+        let required = |key: &str| values.get(key).map(|v| v.to_string()).ok_or_else(|| format!("Missing {} in SIWS message", key));
+        Ok(SiwsMessage {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement: statement.map(|s| s.to_string()),
+            uri: values.get("URI").map(|v| v.to_string()),
+            version: required("Version")?,
+            chain_id: values.get("Chain ID").map(|v| v.to_string()),
+            nonce: required("Nonce")?,
+            issued_at: parse_time(&required("Issued At")?, "Issued At")?,
+            expiration_time: values.get("Expiration Time").map(|v| parse_time(v, "Expiration Time")).transpose()?,
+        })
+    }
+}
+
+pub fn generate_nonce() -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(NONCE_LEN).map(char::from).collect()
+}
+
+struct Challenge {
+    message: String,
+    expires_at: DateTime<Utc>,
+    used: bool,
+}
+
+struct Session {
+    address: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Issued challenges keyed by nonce, and the sessions handed out for them.
+#[derive(Default)]
+pub struct SiwsStore {
+    challenges: Mutex<HashMap<String, Challenge>>,
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SiwsStore {
+    pub fn issue(&self, message: &SiwsMessage, expires_at: DateTime<Utc>) -> Result<(), String> {
+        let mut challenges = self.challenges.lock().unwrap();
+        // used and unused nonces are both kept until they expire, so replays are reported as such
+        let now = Utc::now();
+        challenges.retain(|_, challenge| challenge.expires_at > now);
+        if challenges.len() >= MAX_CHALLENGES {
+            return Err("Too many outstanding challenges".to_string());
+        }
+        challenges.insert(message.nonce.clone(), Challenge { message: message.to_text(), expires_at, used: false });
+        Ok(())
+    }
+
+    /// Marks the nonce used once the message matches what was issued and hasn't expired.
+    pub fn consume(&self, nonce: &str, text: &str, now: DateTime<Utc>) -> Result<(), String> {
+        let mut challenges = self.challenges.lock().unwrap();
+        let challenge = challenges.get_mut(nonce).ok_or_else(|| "Unknown nonce".to_string())?;
+        if challenge.used {
+            return Err("Nonce already used".to_string());
+        }
+        if challenge.expires_at <= now {
+            return Err("Challenge expired".to_string());
+        }
+        if challenge.message != text {
+            return Err("Message does not match the issued challenge".to_string());
+        }
+        challenge.used = true;
+        Ok(())
+    }
+
+    pub fn create_session(&self, address: &str, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = bs58::encode(bytes).into_string();
+        let expires_at = now + Duration::hours(SESSION_TTL_HOURS);
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(token.clone(), Session { address: address.to_string(), expires_at });
+        (token, expires_at)
+    }
+
+    /// Address and expiry of a live session token.
+    pub fn session(&self, token: &str, now: DateTime<Utc>) -> Option<(String, DateTime<Utc>)> {
+        let sessions = self.sessions.lock().unwrap();
+        sessions
+            .get(token)
+            .filter(|session| session.expires_at > now)
+            .map(|session| (session.address.clone(), session.expires_at))
+    }
+}
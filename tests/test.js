@@ -1,8 +1,8 @@
 const axios = require("axios");
 const nacl = require('tweetnacl');
-const { PublicKey, Keypair } = require('@solana/web3.js');
+const { PublicKey, Keypair, Transaction, SystemProgram } = require('@solana/web3.js');
 const bs58 = require('bs58').default;
-const { getAssociatedTokenAddress } = require("@solana/spl-token");
+const { getAssociatedTokenAddress, MintLayout, AccountLayout, MINT_SIZE, ACCOUNT_SIZE } = require("@solana/spl-token");
 
 const HTTP_URL = process.env.HTTP_URL || "http://localhost:8080";
 
@@ -11,6 +11,9 @@ const NOT_FOUND_CODE = 404;
 const SUCCESS_CODE = 200;
 
 const TOKEN_PROGRAM_ID = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ID = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const ASSOCIATED_TOKEN_PROGRAM_ID = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const NATIVE_MINT = "So11111111111111111111111111111111111111112";
 
 describe("Solana Fellowship API", () => {
   let generatedKeypair = null;
@@ -459,4 +462,1617 @@ describe("Solana Fellowship API", () => {
     expect(res.status).toBe(400);
     expect(res.data.success).toBe(false);
   });
+
+  test("POST /token/approve should return approve instruction for the owner's ATA", async () => {
+    const mintKeypair = Keypair.generate();
+    const ownerKeypair = Keypair.generate();
+    const delegateKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/approve`, {
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: ownerKeypair.publicKey.toBase58(),
+      delegate: delegateKeypair.publicKey.toBase58(),
+      amount: 5000,
+    });
+
+    const ata = await getAssociatedTokenAddress(mintKeypair.publicKey, ownerKeypair.publicKey);
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.success).toBe(true);
+    expect(res.data.data.program_id).toBe(TOKEN_PROGRAM_ID);
+    expect(res.data.data.accounts.length).toBe(3); // source, delegate, owner
+    expect(res.data.data.accounts[0].pubkey).toBe(ata.toString());
+    expect(res.data.data.accounts[1].pubkey).toBe(delegateKeypair.publicKey.toBase58());
+    expect(res.data.data.accounts[2].is_signer).toBe(true);
+    expect(bs58.decode(res.data.data.instruction_data)[0]).toBe(4); // Approve
+  });
+
+  test("POST /token/approve should reject owner as delegate", async () => {
+    const ownerKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/approve`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: ownerKeypair.publicKey.toBase58(),
+      delegate: ownerKeypair.publicKey.toBase58(),
+      amount: 5000,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /token/approve-checked should require decimals and include the mint", async () => {
+    const mintKeypair = Keypair.generate();
+    const body = {
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      delegate: Keypair.generate().publicKey.toBase58(),
+      amount: 5000,
+    };
+
+    const missing = await axios.post(`${HTTP_URL}/token/approve-checked`, body, { validateStatus: () => true });
+    expect(missing.status).toBe(ERROR_CODE);
+
+    const res = await axios.post(`${HTTP_URL}/token/approve-checked`, { ...body, decimals: 6 });
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts.length).toBe(4); // source, mint, delegate, owner
+    expect(res.data.data.accounts[1].pubkey).toBe(mintKeypair.publicKey.toBase58());
+  });
+
+  test("POST /token/revoke should return revoke instruction", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/revoke`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts.length).toBe(2); // source, owner
+    expect(bs58.decode(res.data.data.instruction_data)[0]).toBe(5); // Revoke
+  });
+
+  test("POST /send/token should use the delegate as transfer authority", async () => {
+    const delegateKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      delegate: delegateKeypair.publicKey.toBase58(),
+      amount: 1000,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts[2].pubkey).toBe(delegateKeypair.publicKey.toBase58());
+  });
+
+  test("POST /token/set-authority should revoke mint authority when newAuthority is null", async () => {
+    const mintKeypair = Keypair.generate();
+    const authorityKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/set-authority`, {
+      target: "mint",
+      mint: mintKeypair.publicKey.toBase58(),
+      authorityType: "mintTokens",
+      currentAuthority: authorityKeypair.publicKey.toBase58(),
+      newAuthority: null,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts[0].pubkey).toBe(mintKeypair.publicKey.toBase58());
+    expect(res.data.data.accounts[1].is_signer).toBe(true);
+    const data = bs58.decode(res.data.data.instruction_data);
+    expect(data[0]).toBe(6); // SetAuthority
+    expect(data[1]).toBe(0); // MintTokens
+    expect(data[2]).toBe(0); // COption::None
+  });
+
+  test("POST /token/set-authority should target the owner's ATA for account authorities", async () => {
+    const mintKeypair = Keypair.generate();
+    const ownerKeypair = Keypair.generate();
+    const newAuthority = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/set-authority`, {
+      target: "account",
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: ownerKeypair.publicKey.toBase58(),
+      authorityType: "closeAccount",
+      currentAuthority: ownerKeypair.publicKey.toBase58(),
+      newAuthority: newAuthority.publicKey.toBase58(),
+    });
+
+    const ata = await getAssociatedTokenAddress(mintKeypair.publicKey, ownerKeypair.publicKey);
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts[0].pubkey).toBe(ata.toString());
+  });
+
+  test("POST /token/set-authority should reject authority types that don't match the target", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/set-authority`, {
+      target: "mint",
+      mint: Keypair.generate().publicKey.toBase58(),
+      authorityType: "accountOwner",
+      currentAuthority: Keypair.generate().publicKey.toBase58(),
+      newAuthority: Keypair.generate().publicKey.toBase58(),
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /token/close should close the owner's ATA and refund rent to the owner", async () => {
+    const mintKeypair = Keypair.generate();
+    const ownerKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/close`, {
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: ownerKeypair.publicKey.toBase58(),
+    });
+
+    const ata = await getAssociatedTokenAddress(mintKeypair.publicKey, ownerKeypair.publicKey);
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts.length).toBe(3); // account, destination, owner
+    expect(res.data.data.accounts[0].pubkey).toBe(ata.toString());
+    expect(res.data.data.accounts[1].pubkey).toBe(ownerKeypair.publicKey.toBase58());
+    expect(res.data.data.accounts[2].is_signer).toBe(true);
+    expect(bs58.decode(res.data.data.instruction_data)[0]).toBe(9); // CloseAccount
+  });
+
+  test("POST /token/close should honour an explicit account and destination", async () => {
+    const account = Keypair.generate().publicKey.toBase58();
+    const destination = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/token/close`, {
+      account,
+      owner: Keypair.generate().publicKey.toBase58(),
+      destination,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts[0].pubkey).toBe(account);
+    expect(res.data.data.accounts[1].pubkey).toBe(destination);
+  });
+
+  test("POST /token/close should fail without mint or account", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/close`, {
+      owner: Keypair.generate().publicKey.toBase58(),
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /send/token should build transfer_checked when decimals are supplied", async () => {
+    const mintKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      decimals: 6,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.instruction).toBe("transfer_checked");
+    expect(res.data.data.accounts.length).toBe(4); // source, mint, destination, owner
+    expect(res.data.data.accounts[1].pubkey).toBe(mintKeypair.publicKey.toBase58());
+    const data = bs58.decode(res.data.data.instruction_data);
+    expect(data[0]).toBe(12); // TransferChecked
+    expect(data[9]).toBe(6);
+  });
+
+  test("POST /send/token strict mode should require decimals", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      strict: true,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /send/token should reject a strict flag that is not a boolean", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      strict: "true",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("strict must be a boolean");
+  });
+
+  test("POST /token/mint with createAta should prepend idempotent ATA creation", async () => {
+    const mintKeypair = Keypair.generate();
+    const destinationKeypair = Keypair.generate();
+    const authorityKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/mint`, {
+      mint: mintKeypair.publicKey.toBase58(),
+      destination: destinationKeypair.publicKey.toBase58(),
+      authority: authorityKeypair.publicKey.toBase58(),
+      amount: 1000,
+      createAta: true,
+    });
+
+    const ata = await getAssociatedTokenAddress(mintKeypair.publicKey, destinationKeypair.publicKey);
+    const instructions = res.data.data.instructions;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(instructions.length).toBe(2);
+    expect(instructions[0].program_id).toBe(ASSOCIATED_TOKEN_PROGRAM_ID);
+    expect(instructions[0].accounts[0].pubkey).toBe(authorityKeypair.publicKey.toBase58()); // payer
+    expect(instructions[0].accounts[1].pubkey).toBe(ata.toString());
+    expect(bs58.decode(instructions[0].instruction_data)[0]).toBe(1); // CreateIdempotent
+    expect(instructions[1].program_id).toBe(TOKEN_PROGRAM_ID);
+  });
+
+  test("POST /send/token with createAta should use the given payer", async () => {
+    const payerKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      createAta: true,
+      payer: payerKeypair.publicKey.toBase58(),
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.instructions.length).toBe(2);
+    expect(res.data.data.instructions[0].accounts[0].pubkey).toBe(payerKeypair.publicKey.toBase58());
+  });
+
+  test("POST /token/ata should derive the address and optionally build the create instruction", async () => {
+    const mintKeypair = Keypair.generate();
+    const ownerKeypair = Keypair.generate();
+    const body = {
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: ownerKeypair.publicKey.toBase58(),
+    };
+    const ata = await getAssociatedTokenAddress(mintKeypair.publicKey, ownerKeypair.publicKey);
+
+    const derived = await axios.post(`${HTTP_URL}/token/ata`, body);
+    expect(derived.status).toBe(SUCCESS_CODE);
+    expect(derived.data.data.address).toBe(ata.toString());
+    expect(derived.data.data.instruction).toBeNull();
+
+    const created = await axios.post(`${HTTP_URL}/token/ata`, { ...body, create: true });
+    expect(created.data.data.instruction.program_id).toBe(ASSOCIATED_TOKEN_PROGRAM_ID);
+  });
+
+  test("POST /send/token and /token/ata should reject ATA creation flags that are not booleans", async () => {
+    const mint = Keypair.generate().publicKey.toBase58();
+    const owner = Keypair.generate().publicKey.toBase58();
+
+    const send = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint,
+      owner,
+      amount: 1000,
+      createAta: "true",
+    }, { validateStatus: () => true });
+    const ata = await axios.post(`${HTTP_URL}/token/ata`, { mint, owner, create: 1 }, { validateStatus: () => true });
+
+    expect(send.status).toBe(ERROR_CODE);
+    expect(send.data.error).toBe("createAta must be a boolean");
+    expect(ata.status).toBe(ERROR_CODE);
+    expect(ata.data.error).toBe("create must be a boolean");
+  });
+
+  test("POST /token/wrap-sol should return create, transfer and sync_native in order", async () => {
+    const ownerKeypair = Keypair.generate();
+    const lamports = 250000000;
+
+    const res = await axios.post(`${HTTP_URL}/token/wrap-sol`, {
+      owner: ownerKeypair.publicKey.toBase58(),
+      lamports,
+    });
+
+    const wsolAta = await getAssociatedTokenAddress(new PublicKey(NATIVE_MINT), ownerKeypair.publicKey);
+    const instructions = res.data.data.instructions;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.account).toBe(wsolAta.toString());
+    expect(instructions.length).toBe(3);
+    expect(instructions[0].program_id).toBe(ASSOCIATED_TOKEN_PROGRAM_ID);
+    expect(instructions[1].program_id).toBe("11111111111111111111111111111111");
+    expect(instructions[1].accounts[1].pubkey).toBe(wsolAta.toString());
+    expect(instructions[2].program_id).toBe(TOKEN_PROGRAM_ID);
+    expect(bs58.decode(instructions[2].instruction_data)[0]).toBe(17); // SyncNative
+  });
+
+  test("POST /token/wrap-sol should reject zero lamports", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/wrap-sol`, {
+      owner: Keypair.generate().publicKey.toBase58(),
+      lamports: 0,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+  });
+
+  test("POST /token/unwrap-sol should close the wSOL account", async () => {
+    const ownerKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/token/unwrap-sol`, {
+      owner: ownerKeypair.publicKey.toBase58(),
+    });
+
+    const wsolAta = await getAssociatedTokenAddress(new PublicKey(NATIVE_MINT), ownerKeypair.publicKey);
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.instructions.length).toBe(1);
+    expect(res.data.data.instructions[0].accounts[0].pubkey).toBe(wsolAta.toString());
+    expect(bs58.decode(res.data.data.instructions[0].instruction_data)[0]).toBe(9); // CloseAccount
+  });
+
+  test("POST /token/create should target Token-2022 when selected", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/create`, {
+      mintAuthority: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      decimals: 6,
+      tokenProgram: "token-2022",
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe(TOKEN_2022_PROGRAM_ID);
+  });
+
+  test("POST /send/token should derive Token-2022 associated token accounts", async () => {
+    const mintKeypair = Keypair.generate();
+    const destinationKeypair = Keypair.generate();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: destinationKeypair.publicKey.toBase58(),
+      mint: mintKeypair.publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      decimals: 6,
+      tokenProgram: "token-2022",
+    });
+
+    const ata = await getAssociatedTokenAddress(
+      mintKeypair.publicKey,
+      destinationKeypair.publicKey,
+      false,
+      new PublicKey(TOKEN_2022_PROGRAM_ID),
+    );
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe(TOKEN_2022_PROGRAM_ID);
+    expect(res.data.data.accounts[2].pubkey).toBe(ata.toString());
+  });
+
+  test("POST /token/mint should reject unknown token programs", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/mint`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      destination: Keypair.generate().publicKey.toBase58(),
+      authority: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      tokenProgram: "token-2077",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /token/create with extensions should order extension inits before initialize_mint", async () => {
+    const mintKeypair = Keypair.generate();
+    const authority = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/token/create`, {
+      mintAuthority: authority,
+      mint: mintKeypair.publicKey.toBase58(),
+      decimals: 6,
+      tokenProgram: "token-2022",
+      payer: authority,
+      extensions: {
+        transferFee: { basisPoints: 50, maximumFee: 5000, configAuthority: authority },
+        mintCloseAuthority: authority,
+      },
+    });
+
+    const instructions = res.data.data.instructions;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.account_size).toBeGreaterThan(82);
+    expect(res.data.data.rent_exempt_lamports).toBeGreaterThan(0);
+    expect(instructions.length).toBe(4); // create_account, transfer fee, close authority, initialize_mint
+    expect(instructions[0].program_id).toBe("11111111111111111111111111111111");
+    expect(bs58.decode(instructions[1].instruction_data)[0]).toBe(26); // TransferFeeExtension
+    expect(bs58.decode(instructions[2].instruction_data)[0]).toBe(25); // InitializeMintCloseAuthority
+    expect(bs58.decode(instructions[3].instruction_data)[0]).toBe(0); // InitializeMint
+    instructions.slice(1).forEach(ix => expect(ix.program_id).toBe(TOKEN_2022_PROGRAM_ID));
+  });
+
+  test("POST /token/create should reject extensions on the legacy token program", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/create`, {
+      mintAuthority: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      decimals: 6,
+      extensions: { nonTransferable: true },
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /token/create should reject a nonTransferable flag that is not a boolean", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/create`, {
+      mintAuthority: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      decimals: 6,
+      tokenProgram: "token-2022",
+      extensions: { nonTransferable: "true" },
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("nonTransferable must be a boolean");
+  });
+
+  test("POST /token/multisig should build initialize_multisig with create_account when a payer is given", async () => {
+    const multisig = Keypair.generate().publicKey.toBase58();
+    const signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()].map(k => k.publicKey.toBase58());
+
+    const res = await axios.post(`${HTTP_URL}/token/multisig`, {
+      multisig,
+      signers,
+      m: 2,
+      payer: signers[0],
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.account_size).toBe(355);
+    expect(res.data.data.instructions.length).toBe(2);
+    const data = bs58.decode(res.data.data.instruction_data);
+    expect(data[0]).toBe(2); // InitializeMultisig
+    expect(data[1]).toBe(2); // m
+    expect(res.data.data.accounts.slice(2).map(a => a.pubkey)).toEqual(signers);
+  });
+
+  test("POST /token/multisig should reject m greater than the signer count", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/multisig`, {
+      multisig: Keypair.generate().publicKey.toBase58(),
+      signers: [Keypair.generate().publicKey.toBase58()],
+      m: 2,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+  });
+
+  test("POST /token/mint with multisig signers should mark each signer", async () => {
+    const multisig = Keypair.generate().publicKey.toBase58();
+    const signers = [Keypair.generate(), Keypair.generate()].map(k => k.publicKey.toBase58());
+
+    const res = await axios.post(`${HTTP_URL}/token/mint`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      destination: Keypair.generate().publicKey.toBase58(),
+      authority: multisig,
+      amount: 1000,
+      signers,
+    });
+
+    const accounts = res.data.data.accounts;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(accounts.length).toBe(5);
+    expect(accounts[2].pubkey).toBe(multisig);
+    expect(accounts[2].is_signer).toBe(false);
+    expect(accounts[3].is_signer).toBe(true);
+    expect(accounts[4].is_signer).toBe(true);
+  });
+
+  test("POST /send/token with multisig signers should list them as signers", async () => {
+    const signer = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: Keypair.generate().publicKey.toBase58(),
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 1000,
+      signers: [signer],
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.accounts[3].pubkey).toBe(signer);
+    expect(res.data.data.accounts[3].isSigner).toBe(true);
+  });
+
+  test("POST /instruction/decode should decode a /send/sol instruction", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const to = Keypair.generate().publicKey.toBase58();
+    const built = await axios.post(`${HTTP_URL}/send/sol`, { from, to, lamports: 4242 });
+
+    const res = await axios.post(`${HTTP_URL}/instruction/decode`, built.data.data);
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program).toBe("system");
+    expect(res.data.data.type).toBe("transfer");
+    expect(res.data.data.info.lamports).toBe(4242);
+    expect(res.data.data.accounts.map(a => a.name)).toEqual(["source", "destination"]);
+    expect(res.data.data.accounts[1].pubkey).toBe(to);
+  });
+
+  test("POST /instruction/decode should name token accounts and multisig signers", async () => {
+    const signers = [Keypair.generate(), Keypair.generate()].map(k => k.publicKey.toBase58());
+    const built = await axios.post(`${HTTP_URL}/token/mint`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      destination: Keypair.generate().publicKey.toBase58(),
+      authority: Keypair.generate().publicKey.toBase58(),
+      amount: 77,
+      signers,
+    });
+
+    const res = await axios.post(`${HTTP_URL}/instruction/decode`, built.data.data);
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program).toBe("spl-token");
+    expect(res.data.data.type).toBe("mintTo");
+    expect(res.data.data.info.amount).toBe(77);
+    expect(res.data.data.accounts.map(a => a.name)).toEqual(["mint", "destination", "authority", "signer_1", "signer_2"]);
+  });
+
+  test("POST /instruction/decode should accept hex and base64 data", async () => {
+    const budget = await axios.post(`${HTTP_URL}/instruction/decode`, {
+      programId: "ComputeBudget111111111111111111111111111111",
+      data: "02400d0300",
+      encoding: "hex",
+    });
+    expect(budget.data.data.type).toBe("setComputeUnitLimit");
+    expect(budget.data.data.info.units).toBe(200000);
+
+    const memo = await axios.post(`${HTTP_URL}/instruction/decode`, {
+      programId: "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+      data: Buffer.from("deposit-42").toString("base64"),
+      encoding: "base64",
+    });
+    expect(memo.data.data.info.memo).toBe("deposit-42");
+  });
+
+  test("POST /instruction/decode should reject unknown programs", async () => {
+    const res = await axios.post(`${HTTP_URL}/instruction/decode`, {
+      programId: Keypair.generate().publicKey.toBase58(),
+      data: "3Bxs4ffTu9T19DNF",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /transaction/inspect should list signers, accounts and decoded instructions", async () => {
+    const payer = Keypair.generate();
+    const recipient = Keypair.generate().publicKey;
+    const tx = new Transaction({
+      feePayer: payer.publicKey,
+      recentBlockhash: Keypair.generate().publicKey.toBase58(),
+    }).add(SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: recipient, lamports: 5000 }));
+    tx.sign(payer);
+
+    const res = await axios.post(`${HTTP_URL}/transaction/inspect`, {
+      transaction: tx.serialize().toString("base64"),
+    });
+
+    const data = res.data.data;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(data.version).toBe("legacy");
+    expect(data.fee_payer).toBe(payer.publicKey.toBase58());
+    expect(data.recent_blockhash).toBe(tx.recentBlockhash);
+    expect(data.signatures.length).toBe(1);
+    expect(data.signatures[0].valid).toBe(true);
+    expect(data.accounts.find(a => a.pubkey === recipient.toBase58()).is_writable).toBe(true);
+    expect(data.instructions[0].decoded.type).toBe("transfer");
+    expect(data.instructions[0].decoded.info.lamports).toBe(5000);
+  });
+
+  test("POST /transaction/inspect should flag missing signatures", async () => {
+    const payer = Keypair.generate();
+    const tx = new Transaction({
+      feePayer: payer.publicKey,
+      recentBlockhash: Keypair.generate().publicKey.toBase58(),
+    }).add(SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: Keypair.generate().publicKey, lamports: 1 }));
+
+    const res = await axios.post(`${HTTP_URL}/transaction/inspect`, {
+      transaction: tx.serialize({ requireAllSignatures: false }).toString("base64"),
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.signatures[0].present).toBe(false);
+    expect(res.data.data.signatures[0].valid).toBe(false);
+  });
+
+  test("POST /transaction/inspect should reject garbage input", async () => {
+    const res = await axios.post(`${HTTP_URL}/transaction/inspect`, {
+      transaction: "AAAA",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /account/decode should unpack an SPL mint", async () => {
+    const mintAuthority = Keypair.generate().publicKey;
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode({
+      mintAuthorityOption: 1,
+      mintAuthority,
+      supply: BigInt(1000),
+      decimals: 6,
+      isInitialized: true,
+      freezeAuthorityOption: 0,
+      freezeAuthority: PublicKey.default,
+    }, data);
+
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: TOKEN_PROGRAM_ID,
+      data: data.toString("base64"),
+    });
+
+    const info = res.data.data.info;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.type).toBe("mint");
+    expect(info.mintAuthority).toBe(mintAuthority.toBase58());
+    expect(info.supply).toBe(1000);
+    expect(info.decimals).toBe(6);
+    expect(info.freezeAuthority).toBe(null);
+  });
+
+  test("POST /account/decode should unpack a token account", async () => {
+    const mint = Keypair.generate().publicKey;
+    const owner = Keypair.generate().publicKey;
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode({
+      mint,
+      owner,
+      amount: BigInt(55),
+      delegateOption: 0,
+      delegate: PublicKey.default,
+      state: 1,
+      isNativeOption: 0,
+      isNative: BigInt(0),
+      delegatedAmount: BigInt(0),
+      closeAuthorityOption: 0,
+      closeAuthority: PublicKey.default,
+    }, data);
+
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: TOKEN_PROGRAM_ID,
+      data: data.toString("base64"),
+    });
+
+    const info = res.data.data.info;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.type).toBe("account");
+    expect(info.mint).toBe(mint.toBase58());
+    expect(info.owner).toBe(owner.toBase58());
+    expect(info.amount).toBe(55);
+    expect(info.state).toBe("initialized");
+  });
+
+  test("POST /account/decode should list Token-2022 mint extensions", async () => {
+    // mint with MintCloseAuthority (all 5s) and NonTransferable
+    const data = "AQAAAAEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEB6AMAAAAAAAAGAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQMAIAAFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQkAAAA=";
+
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: TOKEN_2022_PROGRAM_ID,
+      data,
+    });
+
+    const extensions = res.data.data.info.extensions;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program).toBe("token-2022");
+    expect(res.data.data.type).toBe("mint");
+    expect(extensions.map(e => e.extension)).toEqual(["mintCloseAuthority", "nonTransferable"]);
+    expect(extensions[0].state.closeAuthority).toBe(new PublicKey(Buffer.alloc(32, 5)).toBase58());
+  });
+
+  test("POST /account/decode should unpack a nonce account", async () => {
+    const authority = Keypair.generate().publicKey;
+    const data = Buffer.alloc(80);
+    data.writeUInt32LE(1, 0);
+    data.writeUInt32LE(1, 4);
+    authority.toBuffer().copy(data, 8);
+    data.writeBigUInt64LE(BigInt(5000), 72);
+
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: "11111111111111111111111111111111",
+      data: data.toString("base64"),
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program).toBe("nonce");
+    expect(res.data.data.type).toBe("initialized");
+    expect(res.data.data.info.authority).toBe(authority.toBase58());
+    expect(res.data.data.info.feeCalculator.lamportsPerSignature).toBe(5000);
+  });
+
+  test("POST /account/decode should reject data of an unknown length", async () => {
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: TOKEN_PROGRAM_ID,
+      data: Buffer.alloc(10).toString("base64"),
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /send/sol should honour the encoding option", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const to = Keypair.generate().publicKey.toBase58();
+
+    const base58 = await axios.post(`${HTTP_URL}/send/sol`, { from, to, lamports: 5 });
+    const base64 = await axios.post(`${HTTP_URL}/send/sol`, { from, to, lamports: 5, encoding: "base64" });
+    const hex = await axios.post(`${HTTP_URL}/send/sol`, { from, to, lamports: 5, encoding: "hex" });
+
+    const bytes = Buffer.from(bs58.decode(base58.data.data.instruction_data));
+    expect(base64.data.data.instruction_data).toBe(bytes.toString("base64"));
+    expect(hex.data.data.instruction_data).toBe(bytes.toString("hex"));
+  });
+
+  test("POST /message/sign and /message/verify should round-trip a hex signature", async () => {
+    const keypair = Keypair.generate();
+    const sign = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "Hello, Solana!",
+      secret: bs58.encode(keypair.secretKey),
+      encoding: "hex",
+    });
+
+    expect(sign.status).toBe(SUCCESS_CODE);
+    expect(sign.data.data.signature).toMatch(/^[0-9a-f]{128}$/);
+
+    const verify = await axios.post(`${HTTP_URL}/message/verify`, {
+      message: "Hello, Solana!",
+      signature: sign.data.data.signature,
+      pubkey: keypair.publicKey.toBase58(),
+      encoding: "hex",
+    });
+
+    expect(verify.status).toBe(SUCCESS_CODE);
+    expect(verify.data.data.valid).toBe(true);
+  });
+
+  test("POST /token/create should reject an unknown encoding", async () => {
+    const res = await axios.post(`${HTTP_URL}/token/create`, {
+      mint: Keypair.generate().publicKey.toBase58(),
+      mintAuthority: Keypair.generate().publicKey.toBase58(),
+      decimals: 6,
+      encoding: "base32",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Unsupported encoding: base32");
+  });
+
+  test("POST /message/sign should sign binary messages given a messageEncoding", async () => {
+    const keypair = Keypair.generate();
+    const bytes = Buffer.from([0xde, 0xad, 0xbe, 0xef]);
+
+    const res = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: bytes.toString("hex"),
+      secret: bs58.encode(keypair.secretKey),
+      messageEncoding: "hex",
+    });
+
+    const data = res.data.data;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(data.message_length).toBe(4);
+    expect(data.message_sha256).toBe(require("crypto").createHash("sha256").update(bytes).digest("hex"));
+    expect(nacl.sign.detached.verify(bytes, bs58.decode(data.signature), keypair.publicKey.toBytes())).toBe(true);
+  });
+
+  test("POST /message/verify should verify base64 messages", async () => {
+    const keypair = Keypair.generate();
+    const bytes = Buffer.from([0, 1, 2, 3, 255]);
+    const signature = nacl.sign.detached(bytes, keypair.secretKey);
+
+    const res = await axios.post(`${HTTP_URL}/message/verify`, {
+      message: bytes.toString("base64"),
+      signature: bs58.encode(signature),
+      pubkey: keypair.publicKey.toBase58(),
+      messageEncoding: "base64",
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.valid).toBe(true);
+    expect(res.data.data.message_length).toBe(5);
+  });
+
+  test("POST /message/sign should reject a message that doesn't match its messageEncoding", async () => {
+    const res = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "not hex",
+      secret: bs58.encode(Keypair.generate().secretKey),
+      messageEncoding: "hex",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Invalid hex message");
+  });
+
+  test("POST /message/sign should sign the SDK's off-chain message envelope by default", async () => {
+    const keypair = Keypair.generate();
+    const message = Buffer.from("Hello, Solana!");
+
+    const res = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: message.toString(),
+      secret: bs58.encode(keypair.secretKey),
+      offchain: true,
+    });
+
+    const length = Buffer.alloc(2);
+    length.writeUInt16LE(message.length);
+    const expected = Buffer.concat([
+      Buffer.from("\xffsolana offchain", "latin1"),
+      Buffer.from([0, 0]),
+      length,
+      message,
+    ]);
+
+    const data = res.data.data;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(data.offchain.header).toBe("legacy");
+    expect(data.offchain.signers).toBeUndefined();
+    expect(data.offchain.format).toBe("ascii");
+    expect(Buffer.from(bs58.decode(data.offchain.serialized_message))).toEqual(expected);
+    expect(nacl.sign.detached.verify(expected, bs58.decode(data.signature), keypair.publicKey.toBytes())).toBe(true);
+  });
+
+  test("POST /message/verify should only accept off-chain signatures in off-chain mode", async () => {
+    const keypair = Keypair.generate();
+    const sign = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "Hello, Solana!",
+      secret: bs58.encode(keypair.secretKey),
+      offchain: { header: "extended", applicationDomain: Keypair.generate().publicKey.toBase58() },
+    });
+    const body = {
+      message: "Hello, Solana!",
+      signature: sign.data.data.signature,
+      pubkey: keypair.publicKey.toBase58(),
+    };
+
+    const offchain = await axios.post(`${HTTP_URL}/message/verify`, {
+      ...body,
+      offchain: { header: "extended", applicationDomain: sign.data.data.offchain.application_domain },
+    });
+    const raw = await axios.post(`${HTTP_URL}/message/verify`, body);
+
+    expect(offchain.data.data.valid).toBe(true);
+    expect(raw.data.data.valid).toBe(false);
+  });
+
+  test("POST /message/sign should require the signing key among the off-chain signers", async () => {
+    const res = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "Hello, Solana!",
+      secret: bs58.encode(Keypair.generate().secretKey),
+      offchain: { header: "extended", signers: [Keypair.generate().publicKey.toBase58()] },
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /auth/siws/challenge and /auth/siws/verify should sign in once per nonce", async () => {
+    const keypair = Keypair.generate();
+    const challenge = await axios.post(`${HTTP_URL}/auth/siws/challenge`, {
+      domain: "example.com",
+      address: keypair.publicKey.toBase58(),
+      statement: "Sign in to Example",
+      chainId: "devnet",
+    });
+
+    const message = challenge.data.data.message;
+    expect(challenge.status).toBe(SUCCESS_CODE);
+    expect(message.startsWith(`example.com wants you to sign in with your Solana account:\n${keypair.publicKey.toBase58()}`)).toBe(true);
+    expect(message).toContain(`Nonce: ${challenge.data.data.nonce}`);
+    expect(message).toContain("Chain ID: devnet");
+
+    const signature = bs58.encode(nacl.sign.detached(Buffer.from(message), keypair.secretKey));
+    const verify = await axios.post(`${HTTP_URL}/auth/siws/verify`, { message, signature });
+
+    expect(verify.status).toBe(SUCCESS_CODE);
+    expect(verify.data.data.address).toBe(keypair.publicKey.toBase58());
+    expect(verify.data.data.token).toBeTruthy();
+
+    const replay = await axios.post(`${HTTP_URL}/auth/siws/verify`, { message, signature }, { validateStatus: () => true });
+    expect(replay.status).toBe(401);
+    expect(replay.data.error).toBe("Nonce already used");
+  });
+
+  test("POST /auth/siws/verify should reject a signature from another key", async () => {
+    const keypair = Keypair.generate();
+    const challenge = await axios.post(`${HTTP_URL}/auth/siws/challenge`, {
+      domain: "example.com",
+      address: keypair.publicKey.toBase58(),
+    });
+    const message = challenge.data.data.message;
+    const signature = bs58.encode(nacl.sign.detached(Buffer.from(message), Keypair.generate().secretKey));
+
+    const res = await axios.post(`${HTTP_URL}/auth/siws/verify`, { message, signature }, { validateStatus: () => true });
+
+    expect(res.status).toBe(401);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /auth/siws/verify should reject a message that was altered after issuing", async () => {
+    const keypair = Keypair.generate();
+    const challenge = await axios.post(`${HTTP_URL}/auth/siws/challenge`, {
+      domain: "example.com",
+      address: keypair.publicKey.toBase58(),
+    });
+    const message = challenge.data.data.message.replace("example.com", "evil.example");
+    const signature = bs58.encode(nacl.sign.detached(Buffer.from(message), keypair.secretKey));
+
+    const res = await axios.post(`${HTTP_URL}/auth/siws/verify`, { message, signature }, { validateStatus: () => true });
+
+    expect(res.status).toBe(401);
+    expect(res.data.error).toBe("Message does not match the issued challenge");
+  });
+
+  test("POST /message/verify-batch should verify every entry", async () => {
+    const keypair = Keypair.generate();
+    const items = ["a", "b", "c"].map(message => ({
+      message,
+      signature: bs58.encode(nacl.sign.detached(Buffer.from(message), keypair.secretKey)),
+      pubkey: keypair.publicKey.toBase58(),
+    }));
+
+    const res = await axios.post(`${HTTP_URL}/message/verify-batch`, { items });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.all_valid).toBe(true);
+    expect(res.data.data.valid_count).toBe(3);
+    expect(res.data.data.results.every(r => r.valid)).toBe(true);
+  });
+
+  test("POST /message/verify-batch should report each failing entry", async () => {
+    const keypair = Keypair.generate();
+    const signature = bs58.encode(nacl.sign.detached(Buffer.from("a"), keypair.secretKey));
+    const pubkey = keypair.publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/message/verify-batch`, [
+      { message: "a", signature, pubkey },
+      { message: "b", signature, pubkey },
+      { message: "a", signature: "abc", pubkey },
+    ]);
+
+    const results = res.data.data.results;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.all_valid).toBe(false);
+    expect(results[0].valid).toBe(true);
+    expect(results[1].valid).toBe(false);
+    expect(results[1].error).toBe("Signature verification failed");
+    expect(results[2].error).toBe("Signature must be 64 bytes");
+  });
+
+  test("POST /message/verify-batch should reject an empty batch", async () => {
+    const res = await axios.post(`${HTTP_URL}/message/verify-batch`, { items: [] }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.success).toBe(false);
+  });
+
+  test("POST /message/sign-batch should return signatures in order", async () => {
+    const keypair = Keypair.generate();
+    const messages = ["voucher-1", "voucher-2", "voucher-3"];
+
+    const res = await axios.post(`${HTTP_URL}/message/sign-batch`, {
+      messages,
+      secret: bs58.encode(keypair.secretKey),
+    });
+
+    const data = res.data.data;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(data.pubkey).toBe(keypair.publicKey.toBase58());
+    expect(data.signatures.length).toBe(3);
+    messages.forEach((message, i) => {
+      expect(nacl.sign.detached.verify(Buffer.from(message), bs58.decode(data.signatures[i]), keypair.publicKey.toBytes())).toBe(true);
+    });
+  });
+
+  test("POST /message/sign-batch should point at the message that failed to decode", async () => {
+    const res = await axios.post(`${HTTP_URL}/message/sign-batch`, {
+      messages: ["00ff", "not hex"],
+      secret: bs58.encode(Keypair.generate().secretKey),
+      messageEncoding: "hex",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Invalid hex message at index 1");
+  });
+
+  test("POST /instruction/ed25519 should pack several signatures with correct offsets", async () => {
+    const signer = Keypair.generate();
+    const other = Keypair.generate();
+    const signature = nacl.sign.detached(Buffer.from("second"), other.secretKey);
+
+    const res = await axios.post(`${HTTP_URL}/instruction/ed25519`, {
+      signatures: [
+        { message: "first", secret: bs58.encode(signer.secretKey) },
+        { message: "second", signature: bs58.encode(signature), pubkey: other.publicKey.toBase58() },
+      ],
+    });
+
+    const data = Buffer.from(bs58.decode(res.data.data.instruction_data));
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe("Ed25519SigVerify111111111111111111111111111");
+    expect(data[0]).toBe(2);
+    [signer, other].forEach((keypair, i) => {
+      const base = 2 + i * 14;
+      const signatureOffset = data.readUInt16LE(base);
+      const pubkeyOffset = data.readUInt16LE(base + 4);
+      const messageOffset = data.readUInt16LE(base + 8);
+      const messageSize = data.readUInt16LE(base + 10);
+      expect(data.readUInt16LE(base + 2)).toBe(0xffff);
+      expect(new PublicKey(data.subarray(pubkeyOffset, pubkeyOffset + 32)).toBase58()).toBe(keypair.publicKey.toBase58());
+      expect(nacl.sign.detached.verify(
+        data.subarray(messageOffset, messageOffset + messageSize),
+        data.subarray(signatureOffset, signatureOffset + 64),
+        keypair.publicKey.toBytes(),
+      )).toBe(true);
+    });
+  });
+
+  test("POST /instruction/ed25519 should refuse a signature that doesn't verify", async () => {
+    const keypair = Keypair.generate();
+    const signature = nacl.sign.detached(Buffer.from("signed"), keypair.secretKey);
+
+    const res = await axios.post(`${HTTP_URL}/instruction/ed25519`, {
+      message: "something else",
+      signature: bs58.encode(signature),
+      pubkey: keypair.publicKey.toBase58(),
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Signature verification failed");
+  });
+
+  test("POST /secp256k1/keypair should return a key and its Ethereum address", async () => {
+    const res = await axios.post(`${HTTP_URL}/secp256k1/keypair`);
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.secret).toMatch(/^[0-9a-f]{64}$/);
+    expect(res.data.data.public_key).toMatch(/^04[0-9a-f]{128}$/);
+    expect(res.data.data.eth_address).toMatch(/^0x[0-9a-fA-F]{40}$/);
+  });
+
+  test("POST /secp256k1/sign should derive the checksummed Ethereum address", async () => {
+    const res = await axios.post(`${HTTP_URL}/secp256k1/sign`, {
+      message: "hello",
+      secret: "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+      encoding: "hex",
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.eth_address).toBe("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+    expect(res.data.data.signature).toMatch(/^[0-9a-f]{128}$/);
+    expect([0, 1]).toContain(res.data.data.recovery_id);
+  });
+
+  test("POST /instruction/secp256k1 should lay out the offsets for each signature", async () => {
+    const keypair = await axios.post(`${HTTP_URL}/secp256k1/keypair`);
+    const signed = await axios.post(`${HTTP_URL}/secp256k1/sign`, { message: "bridge", secret: keypair.data.data.secret });
+
+    const res = await axios.post(`${HTTP_URL}/instruction/secp256k1`, {
+      signatures: [
+        { message: "bridge", signature: signed.data.data.signature, recoveryId: signed.data.data.recovery_id, ethAddress: signed.data.data.eth_address },
+        { message: "second", secret: keypair.data.data.secret },
+      ],
+      instructionIndex: 1,
+    });
+
+    const data = Buffer.from(bs58.decode(res.data.data.instruction_data));
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe("KeccakSecp256k11111111111111111111111111111");
+    expect(data[0]).toBe(2);
+    const signatureOffset = data.readUInt16LE(1);
+    const addressOffset = data.readUInt16LE(4);
+    const messageOffset = data.readUInt16LE(7);
+    const messageSize = data.readUInt16LE(9);
+    expect(data[3]).toBe(1);
+    expect("0x" + data.subarray(addressOffset, addressOffset + 20).toString("hex")).toBe(signed.data.data.eth_address.toLowerCase());
+    expect(bs58.encode(data.subarray(signatureOffset, signatureOffset + 64))).toBe(signed.data.data.signature);
+    expect(data.subarray(messageOffset, messageOffset + messageSize).toString()).toBe("bridge");
+  });
+
+  test("POST /instruction/secp256k1 should refuse a signature from another address", async () => {
+    const keypair = await axios.post(`${HTTP_URL}/secp256k1/keypair`);
+    const other = await axios.post(`${HTTP_URL}/secp256k1/keypair`);
+    const signed = await axios.post(`${HTTP_URL}/secp256k1/sign`, { message: "bridge", secret: keypair.data.data.secret });
+
+    const res = await axios.post(`${HTTP_URL}/instruction/secp256k1`, {
+      message: "bridge",
+      signature: signed.data.data.signature,
+      recoveryId: signed.data.data.recovery_id,
+      ethAddress: other.data.data.eth_address,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Signature verification failed");
+  });
+
+  test("POST /message/verify strict should reject a small-order key that plain verify accepts", async () => {
+    const identity = Buffer.alloc(32);
+    identity[0] = 1;
+    const signature = Buffer.concat([identity, Buffer.alloc(32)]).toString("hex");
+    const body = { message: "anything", signature, pubkey: bs58.encode(identity), encoding: "hex" };
+
+    const lenient = await axios.post(`${HTTP_URL}/message/verify`, body);
+    const strict = await axios.post(`${HTTP_URL}/message/verify`, { ...body, strict: true });
+
+    expect(lenient.data.data.valid).toBe(true);
+    expect(strict.status).toBe(SUCCESS_CODE);
+    expect(strict.data.data.valid).toBe(false);
+    expect(strict.data.data.reason).toBe("small_order_public_key");
+  });
+
+  test("POST /message/verify strict should report a non-canonical S", async () => {
+    // RFC 8032 test 1 with the group order added to S
+    const pubkey = Buffer.from("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a", "hex");
+    const signature = Buffer.from("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b", "hex");
+    const order = Buffer.from("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010", "hex");
+    let carry = 0;
+    for (let i = 0; i < 32; i++) {
+      const sum = signature[32 + i] + order[i] + carry;
+      signature[32 + i] = sum & 0xff;
+      carry = sum >> 8;
+    }
+
+    const res = await axios.post(`${HTTP_URL}/message/verify`, {
+      message: "",
+      signature: signature.toString("hex"),
+      pubkey: bs58.encode(pubkey),
+      encoding: "hex",
+      strict: true,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.valid).toBe(false);
+    expect(res.data.data.reason).toBe("non_canonical_s");
+  });
+
+  test("POST /compute-budget/unit-limit should encode SetComputeUnitLimit", async () => {
+    const res = await axios.post(`${HTTP_URL}/compute-budget/unit-limit`, { units: 200000, encoding: "hex" });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe("ComputeBudget111111111111111111111111111111");
+    expect(res.data.data.accounts).toEqual([]);
+    expect(res.data.data.instruction_data).toBe("02400d0300");
+  });
+
+  test("POST /compute-budget/unit-limit should reject limits above the runtime maximum", async () => {
+    const res = await axios.post(`${HTTP_URL}/compute-budget/unit-limit`, { units: 1400001 }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("units must be between 1 and 1400000");
+  });
+
+  test("POST /compute-budget/unit-price should encode SetComputeUnitPrice", async () => {
+    const res = await axios.post(`${HTTP_URL}/compute-budget/unit-price`, { microLamports: 5000, encoding: "hex" });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.instruction_data).toBe("038813000000000000");
+  });
+
+  test("POST /send/sol should prepend compute budget instructions for a priority fee", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const to = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/send/sol`, {
+      from,
+      to,
+      lamports: 1000,
+      priorityFee: { computeUnitLimit: 450, microLamports: 10000 },
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const programs = res.data.data.instructions.map((ix) => ix.program_id);
+    expect(programs).toEqual([
+      "ComputeBudget111111111111111111111111111111",
+      "ComputeBudget111111111111111111111111111111",
+      "11111111111111111111111111111111",
+    ]);
+    expect(res.data.data.instructions[2].instruction_data).toBe(res.data.data.instruction_data);
+  });
+
+  test("POST /send/token should put the priority fee ahead of ATA creation", async () => {
+    const owner = Keypair.generate().publicKey.toBase58();
+    const destination = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination,
+      mint: "So11111111111111111111111111111111111111112",
+      owner,
+      amount: 5,
+      createAta: true,
+      priorityFee: { microLamports: 1 },
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const programs = res.data.data.instructions.map((ix) => ix.program_id);
+    expect(programs).toEqual([
+      "ComputeBudget111111111111111111111111111111",
+      "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+      "TokenkegQfeZyiNwAJbNbGKPFXCWuBw4y1d5YTQoFrhh",
+    ]);
+  });
+
+  test("POST /send/sol should reject an empty priorityFee block", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/sol`, {
+      from: Keypair.generate().publicKey.toBase58(),
+      to: Keypair.generate().publicKey.toBase58(),
+      lamports: 1000,
+      priorityFee: {},
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("priorityFee needs computeUnitLimit or microLamports");
+  });
+
+  test("POST /memo should build a memo with signer accounts", async () => {
+    const signer = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/memo`, { memo: "deposit 42", signers: [signer] });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.program_id).toBe("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+    expect(res.data.data.accounts).toEqual([{ pubkey: signer, is_signer: true, is_writable: false }]);
+    expect(Buffer.from(bs58.decode(res.data.data.instruction_data)).toString()).toBe("deposit 42");
+  });
+
+  test("POST /memo should reject bytes that are not UTF-8", async () => {
+    const res = await axios.post(`${HTTP_URL}/memo`, { memo: "ff", memoEncoding: "hex" }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Memo must be valid UTF-8");
+  });
+
+  test("POST /memo should reject memos that do not fit a transaction", async () => {
+    const fits = await axios.post(`${HTTP_URL}/memo`, { memo: "x".repeat(1062) });
+    const res = await axios.post(`${HTTP_URL}/memo`, { memo: "x".repeat(1063) }, { validateStatus: () => true });
+
+    expect(fits.status).toBe(SUCCESS_CODE);
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Memo does not fit in the transaction (1233 of 1232 bytes)");
+  });
+
+  test("POST /send/sol should append the memo after the transfer", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/sol`, {
+      from: Keypair.generate().publicKey.toBase58(),
+      to: Keypair.generate().publicKey.toBase58(),
+      lamports: 1000,
+      memo: "invoice 7",
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const [transfer, memo] = res.data.data.instructions;
+    expect(transfer.program_id).toBe("11111111111111111111111111111111");
+    expect(memo.program_id).toBe("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+    expect(Buffer.from(bs58.decode(memo.instruction_data)).toString()).toBe("invoice 7");
+  });
+
+  test("POST /send/token should append the memo after the transfer", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/token`, {
+      destination: Keypair.generate().publicKey.toBase58(),
+      mint: "So11111111111111111111111111111111111111112",
+      owner: Keypair.generate().publicKey.toBase58(),
+      amount: 5,
+      memo: "exchange-tag-1",
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const programs = res.data.data.instructions.map((ix) => ix.program_id);
+    expect(programs).toEqual([
+      "TokenkegQfeZyiNwAJbNbGKPFXCWuBw4y1d5YTQoFrhh",
+      "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+    ]);
+  });
+
+  test("POST /stake/create should create then initialize the stake account", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const stakeAccount = Keypair.generate().publicKey.toBase58();
+    const custodian = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/stake/create`, {
+      from,
+      stakeAccount,
+      staker: from,
+      lamports: 5000000,
+      lockup: { epoch: 10, custodian },
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.stake_account).toBe(stakeAccount);
+    const [create, initialize] = res.data.data.instructions;
+    expect(create.program_id).toBe("11111111111111111111111111111111");
+    expect(create.accounts[1]).toEqual({ pubkey: stakeAccount, is_signer: true, is_writable: true });
+    expect(initialize.program_id).toBe("Stake11111111111111111111111111111111111111");
+  });
+
+  test("POST /stake/create should require the rent-exempt reserve", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/stake/create`, {
+      from,
+      stakeAccount: Keypair.generate().publicKey.toBase58(),
+      staker: from,
+      lamports: 1000,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toMatch(/rent-exempt reserve/);
+  });
+
+  test("POST /stake/delegate should list the stake, vote and staker accounts", async () => {
+    const stakeAccount = Keypair.generate().publicKey.toBase58();
+    const staker = Keypair.generate().publicKey.toBase58();
+    const voteAccount = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/stake/delegate`, { stakeAccount, staker, voteAccount });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const [delegate] = res.data.data.instructions;
+    expect(delegate.accounts[0].pubkey).toBe(stakeAccount);
+    expect(delegate.accounts[1].pubkey).toBe(voteAccount);
+    expect(delegate.accounts[5]).toEqual({ pubkey: staker, is_signer: true, is_writable: false });
+  });
+
+  test("POST /stake/split should fund the split account when a payer is given", async () => {
+    const stakeAccount = Keypair.generate().publicKey.toBase58();
+    const staker = Keypair.generate().publicKey.toBase58();
+    const splitStakeAccount = Keypair.generate().publicKey.toBase58();
+
+    const res = await axios.post(`${HTTP_URL}/stake/split`, {
+      stakeAccount,
+      staker,
+      splitStakeAccount,
+      lamports: 1000000,
+      payer: staker,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.stake_account).toBe(stakeAccount);
+    expect(res.data.data.split_stake_account).toBe(splitStakeAccount);
+    const programs = res.data.data.instructions.map((ix) => ix.program_id);
+    expect(programs).toEqual([
+      "11111111111111111111111111111111",
+      "11111111111111111111111111111111",
+      "11111111111111111111111111111111",
+      "Stake11111111111111111111111111111111111111",
+    ]);
+  });
+
+  test("POST /stake/authorize should reject unknown authority types", async () => {
+    const res = await axios.post(`${HTTP_URL}/stake/authorize`, {
+      stakeAccount: Keypair.generate().publicKey.toBase58(),
+      authority: Keypair.generate().publicKey.toBase58(),
+      newAuthority: Keypair.generate().publicKey.toBase58(),
+      authorityType: "voter",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Unsupported authorityType: voter");
+  });
+
+  test("POST /send/batch should split SOL payouts across transactions by size", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const recipients = Array.from({ length: 60 }, (_, i) => ({
+      recipient: Keypair.generate().publicKey.toBase58(),
+      amount: 1000 + i,
+    }));
+
+    const res = await axios.post(`${HTTP_URL}/send/batch`, { from, recipients });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(res.data.data.transfer_count).toBe(60);
+    const { transactions } = res.data.data;
+    expect(transactions.length).toBeGreaterThan(1);
+    expect(transactions.flatMap((tx) => tx.recipients)).toEqual(recipients.map((_, i) => i));
+    for (const tx of transactions) {
+      expect(tx.size).toBeLessThanOrEqual(1232);
+      expect(tx.instructions.every((ix) => ix.program_id === "11111111111111111111111111111111")).toBe(true);
+    }
+  });
+
+  test("POST /send/batch should respect the compute unit limit and repeat the priority fee", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const recipients = Array.from({ length: 10 }, () => ({
+      recipient: Keypair.generate().publicKey.toBase58(),
+      amount: 1,
+    }));
+
+    const res = await axios.post(`${HTTP_URL}/send/batch`, {
+      from,
+      recipients,
+      priorityFee: { computeUnitLimit: 1000, microLamports: 5 },
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const { transactions } = res.data.data;
+    expect(transactions.map((tx) => tx.recipients)).toEqual([[0, 1, 2, 3], [4, 5, 6, 7], [8, 9]]);
+    for (const tx of transactions) {
+      expect(tx.compute_units).toBeLessThanOrEqual(1000);
+      expect(tx.instructions[0].program_id).toBe("ComputeBudget111111111111111111111111111111");
+      expect(tx.instructions[1].program_id).toBe("ComputeBudget111111111111111111111111111111");
+    }
+  });
+
+  test("POST /send/batch should build token transfers with ATA creation", async () => {
+    const from = Keypair.generate().publicKey.toBase58();
+    const recipients = Array.from({ length: 3 }, () => ({
+      recipient: Keypair.generate().publicKey.toBase58(),
+      amount: 5,
+    }));
+
+    const res = await axios.post(`${HTTP_URL}/send/batch`, {
+      from,
+      recipients,
+      mint: "So11111111111111111111111111111111111111112",
+      decimals: 9,
+      createAta: true,
+    });
+
+    expect(res.status).toBe(SUCCESS_CODE);
+    const [tx] = res.data.data.transactions;
+    expect(tx.recipients).toEqual([0, 1, 2]);
+    const programs = tx.instructions.map((ix) => ix.program_id);
+    expect(programs).toEqual([
+      "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+      "TokenkegQfeZyiNwAJbNbGKPFXCWuBw4y1d5YTQoFrhh",
+      "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+      "TokenkegQfeZyiNwAJbNbGKPFXCWuBw4y1d5YTQoFrhh",
+      "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+      "TokenkegQfeZyiNwAJbNbGKPFXCWuBw4y1d5YTQoFrhh",
+    ]);
+  });
+
+  test("POST /send/batch should point at the invalid recipient", async () => {
+    const res = await axios.post(`${HTTP_URL}/send/batch`, {
+      from: Keypair.generate().publicKey.toBase58(),
+      recipients: [
+        { recipient: Keypair.generate().publicKey.toBase58(), amount: 1 },
+        { recipient: Keypair.generate().publicKey.toBase58(), amount: 0 },
+      ],
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Amount must be greater than 0 at index 1");
+  });
+
+  test("POST /token/approve, /token/revoke and /token/close should accept multisig signers", async () => {
+    const multisig = Keypair.generate().publicKey.toBase58();
+    const mint = Keypair.generate().publicKey.toBase58();
+    const delegate = Keypair.generate().publicKey.toBase58();
+    const signers = [Keypair.generate().publicKey.toBase58(), Keypair.generate().publicKey.toBase58()];
+
+    const requests = [
+      ["/token/approve", { mint, owner: multisig, delegate, amount: 10, signers }],
+      ["/token/revoke", { mint, owner: multisig, signers }],
+      ["/token/close", { mint, owner: multisig, signers }],
+    ];
+    for (const [path, body] of requests) {
+      const res = await axios.post(`${HTTP_URL}${path}`, body);
+
+      expect(res.status).toBe(SUCCESS_CODE);
+      const accounts = res.data.data.accounts;
+      expect(accounts.slice(-3)).toEqual([
+        { pubkey: multisig, is_signer: false, is_writable: false },
+        { pubkey: signers[0], is_signer: true, is_writable: false },
+        { pubkey: signers[1], is_signer: true, is_writable: false },
+      ]);
+    }
+  });
+
+  test("POST /account/decode should reject signed hex digits", async () => {
+    const data = Buffer.alloc(MINT_SIZE);
+    data[0] = 1;
+    // "+1" used to parse as the byte 0x01
+    const hex = "+1" + data.toString("hex").slice(2);
+
+    const res = await axios.post(`${HTTP_URL}/account/decode`, {
+      owner: TOKEN_PROGRAM_ID,
+      data: hex,
+      encoding: "hex",
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Invalid hex data");
+  });
+
+  test("POST /auth/siws/session should resolve the token issued by verify", async () => {
+    const keypair = Keypair.generate();
+    const challenge = await axios.post(`${HTTP_URL}/auth/siws/challenge`, {
+      domain: "example.com",
+      address: keypair.publicKey.toBase58(),
+    });
+    const message = challenge.data.data.message;
+    const signature = bs58.encode(nacl.sign.detached(Buffer.from(message), keypair.secretKey));
+    const verify = await axios.post(`${HTTP_URL}/auth/siws/verify`, { message, signature });
+
+    const session = await axios.post(`${HTTP_URL}/auth/siws/session`, { token: verify.data.data.token });
+
+    expect(session.status).toBe(SUCCESS_CODE);
+    expect(session.data.data.address).toBe(keypair.publicKey.toBase58());
+    expect(session.data.data.expires_at).toBe(verify.data.data.expires_at);
+
+    const unknown = await axios.post(`${HTTP_URL}/auth/siws/session`, { token: "not-a-token" }, { validateStatus: () => true });
+    expect(unknown.status).toBe(401);
+    expect(unknown.data.error).toBe("Invalid or expired session");
+  });
+
+  test("POST /auth/siws/challenge should reject an expiresIn longer than 15 minutes", async () => {
+    const res = await axios.post(`${HTTP_URL}/auth/siws/challenge`, {
+      domain: "example.com",
+      address: Keypair.generate().publicKey.toBase58(),
+      expiresIn: 901,
+    }, { validateStatus: () => true });
+
+    expect(res.status).toBe(400);
+    expect(res.data.error).toBe("expiresIn must be between 1 and 900 seconds");
+  });
+
+  test("POST /message/sign should write the extended header with offchain.header extended", async () => {
+    const keypair = Keypair.generate();
+    const message = Buffer.from("Hello, Solana!");
+
+    const res = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: message.toString(),
+      secret: bs58.encode(keypair.secretKey),
+      offchain: { header: "extended" },
+    });
+
+    const length = Buffer.alloc(2);
+    length.writeUInt16LE(message.length);
+    const expected = Buffer.concat([
+      Buffer.from("\xffsolana offchain", "latin1"),
+      Buffer.from([0]),
+      Buffer.alloc(32),
+      Buffer.from([0, 1]),
+      keypair.publicKey.toBuffer(),
+      length,
+      message,
+    ]);
+
+    const data = res.data.data;
+    expect(res.status).toBe(SUCCESS_CODE);
+    expect(data.offchain.header).toBe("extended");
+    expect(data.offchain.signers).toEqual([keypair.publicKey.toBase58()]);
+    expect(Buffer.from(bs58.decode(data.offchain.serialized_message))).toEqual(expected);
+    expect(nacl.sign.detached.verify(expected, bs58.decode(data.signature), keypair.publicKey.toBytes())).toBe(true);
+  });
+
+  test("POST /send/sol should leave the memo only the room left after the transfer", async () => {
+    const body = {
+      from: Keypair.generate().publicKey.toBase58(),
+      to: Keypair.generate().publicKey.toBase58(),
+      lamports: 1000,
+    };
+
+    const fits = await axios.post(`${HTTP_URL}/send/sol`, { ...body, memo: "x".repeat(981) });
+    const res = await axios.post(`${HTTP_URL}/send/sol`, { ...body, memo: "x".repeat(982) }, { validateStatus: () => true });
+
+    expect(fits.status).toBe(SUCCESS_CODE);
+    expect(res.status).toBe(ERROR_CODE);
+    expect(res.data.error).toBe("Memo does not fit in the transaction (1233 of 1232 bytes)");
+  });
+
+  test("POST /keypair should honor encoding while message signing keeps taking a base58 secret", async () => {
+    const keypair = await axios.post(`${HTTP_URL}/keypair`, { encoding: "hex" });
+    const secret = Buffer.from(keypair.data.data.secret, "hex");
+    expect(keypair.status).toBe(SUCCESS_CODE);
+    expect(secret.length).toBe(64);
+    expect(bs58.encode(secret.subarray(32))).toBe(keypair.data.data.pubkey);
+
+    // `encoding` here is the signature's; the secret is still base58
+    const sign = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "hello",
+      secret: bs58.encode(secret),
+      encoding: "hex",
+    });
+    const hexSecret = await axios.post(`${HTTP_URL}/message/sign`, {
+      message: "hello",
+      secret: keypair.data.data.secret,
+      encoding: "hex",
+    }, { validateStatus: () => true });
+
+    expect(sign.status).toBe(SUCCESS_CODE);
+    expect(Buffer.from(sign.data.data.signature, "hex").length).toBe(64);
+    expect(hexSecret.status).toBe(ERROR_CODE);
+  });
 })